@@ -0,0 +1,88 @@
+//! Amount conservation of RingCT transactions.
+//!
+//! The amounts being hidden, a transaction balances when its commitments do:
+//!
+//! ```text
+//! pseudo_commitment == sum(output commitments) + fee * H
+//! ```
+//!
+//! which holds only if the blinding factors cancel out and the amounts match, the range proofs
+//! of the outputs preventing negative amounts from wrapping around the group order. The
+//! transaction has exactly one pseudo commitment, and its ring signature proves that it commits
+//! to the amount of the spent ring member (see `ring_signature`), so the check only holds for the
+//! value actually spent.
+use crate::crypto::{commit, point_from_hex, point_to_hex};
+use crate::interface::PendingRingCT;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use k256::{ProjectivePoint, Scalar};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalanceError {
+    PseudoCommitmentCount(usize),
+    InvalidPseudoCommitment,
+    InvalidOutputCommitment(usize),
+    Imbalance(String), // pseudo_commitment - sum(outputs) - fee * H, compressed point hex
+}
+
+impl Display for BalanceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            BalanceError::PseudoCommitmentCount(count) => {
+                write!(f, "{} pseudo commitments, expected 1", count)
+            }
+            BalanceError::InvalidPseudoCommitment => write!(f, "Invalid pseudo commitment"),
+            BalanceError::InvalidOutputCommitment(index) => {
+                write!(f, "Invalid commitment for output {}", index)
+            }
+            BalanceError::Imbalance(difference) => {
+                write!(
+                    f,
+                    "Commitments do not balance, difference is {}",
+                    difference
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BalanceError {}
+
+/// Checks that the pseudo commitment of the spent input is the sum of the output commitments plus
+/// the fee.
+pub fn verify_balance(
+    pseudo_commitment: &str,
+    output_commitments: &[String],
+    fee: u64,
+) -> Result<(), BalanceError> {
+    let mut difference = point_from_hex(pseudo_commitment)
+        .map_err(|_| BalanceError::InvalidPseudoCommitment)?
+        - commit(fee, &Scalar::ZERO);
+    for (index, commitment) in output_commitments.iter().enumerate() {
+        difference -=
+            point_from_hex(commitment).map_err(|_| BalanceError::InvalidOutputCommitment(index))?;
+    }
+    if difference == ProjectivePoint::IDENTITY {
+        Ok(())
+    } else {
+        Err(BalanceError::Imbalance(point_to_hex(&difference)))
+    }
+}
+
+impl PendingRingCT {
+    /// Checks that the transaction creates no value, see `verify_balance`.
+    pub fn verify_balance(&self) -> Result<(), BalanceError> {
+        let pseudo_commitment = match self.pseudo_commitments.as_slice() {
+            [commitment] => commitment,
+            commitments => return Err(BalanceError::PseudoCommitmentCount(commitments.len())),
+        };
+        let outputs: Vec<String> = self
+            .outputs
+            .iter()
+            .map(|output| output.get_commitment())
+            .collect();
+        verify_balance(pseudo_commitment, &outputs, self.fee)
+    }
+}
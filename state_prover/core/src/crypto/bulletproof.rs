@@ -0,0 +1,396 @@
+//! Bulletproof range proof verification.
+//!
+//! A `LigthRangeProof` proves that its commitment `V = gamma * G + v * H` opens to a value
+//! `0 <= v < 2^64`. Its fields map to the paper notation as follows: `tx` is `t_hat`, `txbf` is
+//! `tau_x`, `e` is `mu`, `a0`/`b0` are the final scalars of the inner product argument and `ind`
+//! holds its `L`/`R` pairs, one per round.
+//!
+//! Generators:
+//!
+//! ```text
+//! H    = H_p(G)
+//! G_i  = H_p("bulletproof.G" || i)        i = 0..64, 4 bytes big-endian
+//! H_i  = H_p("bulletproof.H" || i)
+//! U    = H_p("bulletproof.U")
+//! ```
+//!
+//! Fiat-Shamir challenges, over compressed points and 32 bytes scalars:
+//!
+//! ```text
+//! y    = H_s(V || A || S)
+//! z    = H_s(V || A || S || y)
+//! x    = H_s(y || z || T1 || T2)
+//! x_ip = H_s(x || t_hat || tau_x || mu)
+//! u_j  = H_s(u_(j-1) || L_j || R_j)      with u_(-1) = x_ip
+//! ```
+//!
+//! The inner product argument runs on `(G_i, y^-i * H_i)` with `U' = x_ip * U`, halving the
+//! vectors into `lo || hi` at every round: `L = <a_lo, G_hi> + <b_hi, H_lo> + <a_lo, b_hi> U'`,
+//! `R = <a_hi, G_lo> + <b_lo, H_hi> + <a_hi, b_lo> U'`, then `a' = u a_lo + u^-1 a_hi` and
+//! `b' = u^-1 b_lo + u b_hi`.
+use crate::crypto::{
+    decode_hex, generator_h, hash_to_point, hash_to_scalar, point_from_bytes, point_to_bytes,
+    scalar_from_hex,
+};
+use crate::interface::{LigthRangeProof, RangeProof};
+use crate::keccak256;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use k256::{ProjectivePoint, Scalar};
+
+/// Number of bits covered by a range proof.
+pub const RANGE_BITS: usize = 64;
+/// Number of inner product rounds, `log2(RANGE_BITS)`.
+pub const RANGE_ROUNDS: usize = 6;
+
+/// secp256k1 group order, as carried by `RangeProof.order`.
+const ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeProofError {
+    InvalidEncoding(usize, &'static str), // proof index, field
+    WrongRounds(usize, usize),            // proof index, number of L/R pairs
+    CommitmentMismatch(usize),            // proof index
+    InvalidGenerator,
+    InvalidOrder,
+    InvalidProof(usize), // proof index
+    InvalidBatch,
+}
+
+impl Display for RangeProofError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            RangeProofError::InvalidEncoding(index, field) => {
+                write!(f, "Range proof {} has an invalid {}", index, field)
+            }
+            RangeProofError::WrongRounds(index, rounds) => write!(
+                f,
+                "Range proof {} has {} rounds, expected {}",
+                index, rounds, RANGE_ROUNDS
+            ),
+            RangeProofError::CommitmentMismatch(index) => {
+                write!(
+                    f,
+                    "Range proof {} is not about the output commitment",
+                    index
+                )
+            }
+            RangeProofError::InvalidGenerator => write!(f, "Range proof uses another generator"),
+            RangeProofError::InvalidOrder => write!(f, "Range proof uses another group order"),
+            RangeProofError::InvalidProof(index) => {
+                write!(f, "Range proof {} does not verify", index)
+            }
+            RangeProofError::InvalidBatch => write!(f, "Range proof batch does not verify"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RangeProofError {}
+
+impl RangeProofError {
+    /// Index of the offending proof in the verified batch, if known.
+    pub fn proof_index(&self) -> Option<usize> {
+        match self {
+            RangeProofError::InvalidEncoding(index, _)
+            | RangeProofError::WrongRounds(index, _)
+            | RangeProofError::CommitmentMismatch(index)
+            | RangeProofError::InvalidProof(index) => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+/// Generators used by the range proofs. Deriving them takes `2 * RANGE_BITS + 2` hashes to the
+/// curve, so they are built once by the caller and shared by every verification.
+pub struct Generators {
+    pub g: Vec<ProjectivePoint>,
+    pub h: Vec<ProjectivePoint>,
+    pub u: ProjectivePoint,
+    pub value: ProjectivePoint,
+}
+
+impl Generators {
+    pub fn new() -> Self {
+        Generators {
+            g: (0..RANGE_BITS)
+                .map(|i| indexed_generator(b"bulletproof.G", i))
+                .collect(),
+            h: (0..RANGE_BITS)
+                .map(|i| indexed_generator(b"bulletproof.H", i))
+                .collect(),
+            u: hash_to_point(b"bulletproof.U"),
+            value: generator_h(),
+        }
+    }
+}
+
+impl Default for Generators {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn indexed_generator(label: &[u8], index: usize) -> ProjectivePoint {
+    let mut data = label.to_vec();
+    data.extend_from_slice(&(index as u32).to_be_bytes());
+    hash_to_point(&data)
+}
+
+impl LigthRangeProof {
+    /// Verifies that the proof is about `commitment` and that it opens to a 64 bits value.
+    pub fn verify(&self, generators: &Generators, commitment: &str) -> Result<(), RangeProofError> {
+        verify_batch(generators, &[(self, commitment)])
+    }
+}
+
+impl RangeProof {
+    /// Verifies the proof after checking that it was produced for secp256k1.
+    pub fn verify(&self, generators: &Generators, commitment: &str) -> Result<(), RangeProofError> {
+        let generator = decode_hex(&self.G)
+            .ok()
+            .and_then(|bytes| point_from_bytes(&bytes))
+            .ok_or(RangeProofError::InvalidGenerator)?;
+        if generator != ProjectivePoint::GENERATOR {
+            return Err(RangeProofError::InvalidGenerator);
+        }
+        let order = decode_hex(&self.order).map_err(|_| RangeProofError::InvalidOrder)?;
+        if hex::encode(order) != ORDER {
+            return Err(RangeProofError::InvalidOrder);
+        }
+        self.to_light().verify(generators, commitment)
+    }
+
+    pub fn to_light(&self) -> LigthRangeProof {
+        LigthRangeProof {
+            V: self.V.clone(),
+            A: self.A.clone(),
+            S: self.S.clone(),
+            T1: self.T1.clone(),
+            T2: self.T2.clone(),
+            tx: self.tx.clone(),
+            txbf: self.txbf.clone(),
+            e: self.e.clone(),
+            a0: self.a0.clone(),
+            b0: self.b0.clone(),
+            ind: self.ind.clone(),
+        }
+    }
+}
+
+/// Verifies several range proofs at once, each one against its commitment.
+///
+/// All the verification equations are folded into a single one with weights derived from the
+/// whole batch, so the generators are only multiplied once. When the batch fails, the proofs are
+/// checked one by one to report the first invalid one.
+pub fn verify_batch(
+    generators: &Generators,
+    proofs: &[(&LigthRangeProof, &str)],
+) -> Result<(), RangeProofError> {
+    if proofs.is_empty() {
+        return Ok(());
+    }
+    let mut parsed = Vec::with_capacity(proofs.len());
+    for (index, (proof, commitment)) in proofs.iter().enumerate() {
+        let proof = ParsedProof::parse(index, proof)?;
+        let commitment = decode_hex(commitment)
+            .ok()
+            .and_then(|bytes| point_from_bytes(&bytes))
+            .ok_or(RangeProofError::CommitmentMismatch(index))?;
+        if commitment != proof.v {
+            return Err(RangeProofError::CommitmentMismatch(index));
+        }
+        parsed.push(proof);
+    }
+
+    if check(generators, &parsed) {
+        return Ok(());
+    }
+    for (index, proof) in parsed.iter().enumerate() {
+        if !check(generators, core::slice::from_ref(proof)) {
+            return Err(RangeProofError::InvalidProof(index));
+        }
+    }
+    Err(RangeProofError::InvalidBatch)
+}
+
+struct ParsedProof {
+    v: ProjectivePoint,
+    a: ProjectivePoint,
+    s: ProjectivePoint,
+    t1: ProjectivePoint,
+    t2: ProjectivePoint,
+    t_hat: Scalar,
+    tau_x: Scalar,
+    mu: Scalar,
+    a0: Scalar,
+    b0: Scalar,
+    l: Vec<ProjectivePoint>,
+    r: Vec<ProjectivePoint>,
+    transcript: Vec<u8>,
+}
+
+impl ParsedProof {
+    fn parse(index: usize, proof: &LigthRangeProof) -> Result<Self, RangeProofError> {
+        if proof.ind.len() != RANGE_ROUNDS {
+            return Err(RangeProofError::WrongRounds(index, proof.ind.len()));
+        }
+        let mut transcript = Vec::new();
+        let mut point = |value: &str, field: &'static str| {
+            let bytes =
+                decode_hex(value).map_err(|_| RangeProofError::InvalidEncoding(index, field))?;
+            let point =
+                point_from_bytes(&bytes).ok_or(RangeProofError::InvalidEncoding(index, field))?;
+            transcript.extend(point_to_bytes(&point));
+            Ok::<_, RangeProofError>(point)
+        };
+        let v = point(&proof.V, "V")?;
+        let a = point(&proof.A, "A")?;
+        let s = point(&proof.S, "S")?;
+        let t1 = point(&proof.T1, "T1")?;
+        let t2 = point(&proof.T2, "T2")?;
+        let mut l = Vec::with_capacity(RANGE_ROUNDS);
+        let mut r = Vec::with_capacity(RANGE_ROUNDS);
+        for pair in &proof.ind {
+            l.push(point(&pair.L, "L")?);
+            r.push(point(&pair.R, "R")?);
+        }
+        let scalar = |value: &str, field: &'static str| {
+            scalar_from_hex(value).map_err(|_| RangeProofError::InvalidEncoding(index, field))
+        };
+        let t_hat = scalar(&proof.tx, "tx")?;
+        let tau_x = scalar(&proof.txbf, "txbf")?;
+        let mu = scalar(&proof.e, "e")?;
+        let a0 = scalar(&proof.a0, "a0")?;
+        let b0 = scalar(&proof.b0, "b0")?;
+        for value in [t_hat, tau_x, mu, a0, b0] {
+            transcript.extend_from_slice(&value.to_bytes());
+        }
+        Ok(ParsedProof {
+            v,
+            a,
+            s,
+            t1,
+            t2,
+            t_hat,
+            tau_x,
+            mu,
+            a0,
+            b0,
+            l,
+            r,
+            transcript,
+        })
+    }
+}
+
+fn challenge(parts: &[&[u8]]) -> Scalar {
+    hash_to_scalar(&parts.concat())
+}
+
+/// Checks the folded verification equations of `proofs`, which must sum to the identity.
+fn check(generators: &Generators, proofs: &[ParsedProof]) -> bool {
+    let mut seed = b"bulletproof.batch".to_vec();
+    for proof in proofs {
+        seed.extend_from_slice(&proof.transcript);
+    }
+    let seed = keccak256(&seed);
+
+    let two = Scalar::from(2u64);
+    let mut g_coefficients = [Scalar::ZERO; RANGE_BITS];
+    let mut h_coefficients = [Scalar::ZERO; RANGE_BITS];
+    let mut g_coefficient = Scalar::ZERO;
+    let mut value_coefficient = Scalar::ZERO;
+    let mut u_coefficient = Scalar::ZERO;
+    let mut total = ProjectivePoint::IDENTITY;
+
+    for (index, proof) in proofs.iter().enumerate() {
+        let (v, a, s) = (
+            point_to_bytes(&proof.v),
+            point_to_bytes(&proof.a),
+            point_to_bytes(&proof.s),
+        );
+        let y = challenge(&[&v, &a, &s]);
+        let z = challenge(&[&v, &a, &s, &y.to_bytes()]);
+        let x = challenge(&[
+            &y.to_bytes(),
+            &z.to_bytes(),
+            &point_to_bytes(&proof.t1),
+            &point_to_bytes(&proof.t2),
+        ]);
+        let x_ip = challenge(&[
+            &x.to_bytes(),
+            &proof.t_hat.to_bytes(),
+            &proof.tau_x.to_bytes(),
+            &proof.mu.to_bytes(),
+        ]);
+        let mut u = Vec::with_capacity(RANGE_ROUNDS);
+        let mut previous = x_ip;
+        for round in 0..RANGE_ROUNDS {
+            previous = challenge(&[
+                &previous.to_bytes(),
+                &point_to_bytes(&proof.l[round]),
+                &point_to_bytes(&proof.r[round]),
+            ]);
+            u.push(previous);
+        }
+        let y_inverse: Option<Scalar> = y.invert().into();
+        let u_inverse: Option<Vec<Scalar>> = u.iter().map(|u| Option::from(u.invert())).collect();
+        let (y_inverse, u_inverse) = match (y_inverse, u_inverse) {
+            (Some(y_inverse), Some(u_inverse)) => (y_inverse, u_inverse),
+            _ => return false,
+        };
+
+        let weight = |label: &[u8]| challenge(&[&seed, label, &(index as u32).to_be_bytes()]);
+        let (c, d) = (weight(b"c"), weight(b"d"));
+        let z2 = z * z;
+
+        let mut y_power = Scalar::ONE;
+        let mut y_inverse_power = Scalar::ONE;
+        let mut two_power = Scalar::ONE;
+        let mut sum_y = Scalar::ZERO;
+        let mut sum_two = Scalar::ZERO;
+        for i in 0..RANGE_BITS {
+            // s_i picks u_j when bit j of i (most significant first) is set, u_j^-1 otherwise
+            let mut s_i = Scalar::ONE;
+            let mut s_i_inverse = Scalar::ONE;
+            for round in 0..RANGE_ROUNDS {
+                if (i >> (RANGE_ROUNDS - 1 - round)) & 1 == 1 {
+                    s_i *= u[round];
+                    s_i_inverse *= u_inverse[round];
+                } else {
+                    s_i *= u_inverse[round];
+                    s_i_inverse *= u[round];
+                }
+            }
+            g_coefficients[i] += d * (-z - proof.a0 * s_i);
+            h_coefficients[i] +=
+                d * (z + (z2 * two_power - proof.b0 * s_i_inverse) * y_inverse_power);
+
+            sum_y += y_power;
+            sum_two += two_power;
+            y_power *= y;
+            y_inverse_power *= y_inverse;
+            two_power *= two;
+        }
+        let delta = (z - z2) * sum_y - z2 * z * sum_two;
+
+        g_coefficient += -(c * proof.tau_x) - d * proof.mu;
+        value_coefficient += c * (delta - proof.t_hat);
+        u_coefficient += d * x_ip * (proof.t_hat - proof.a0 * proof.b0);
+        total += proof.v * (c * z2) + proof.t1 * (c * x) + proof.t2 * (c * x * x);
+        total += proof.a * d + proof.s * (d * x);
+        for round in 0..RANGE_ROUNDS {
+            total += proof.l[round] * (d * u[round] * u[round]);
+            total += proof.r[round] * (d * u_inverse[round] * u_inverse[round]);
+        }
+    }
+
+    total += ProjectivePoint::GENERATOR * g_coefficient;
+    total += generators.value * value_coefficient;
+    total += generators.u * u_coefficient;
+    for i in 0..RANGE_BITS {
+        total += generators.g[i] * g_coefficients[i] + generators.h[i] * h_coefficients[i];
+    }
+    total == ProjectivePoint::IDENTITY
+}
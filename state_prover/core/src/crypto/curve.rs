@@ -0,0 +1,127 @@
+//! secp256k1 helpers shared by the verifiers.
+//!
+//! Points travel as hex encoded SEC1 bytes (compressed unless stated otherwise), scalars as 32
+//! bytes big-endian hex. Both accept an optional `0x` prefix.
+use crate::keccak256;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+use k256::elliptic_curve::ops::Reduce;
+use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use k256::elliptic_curve::PrimeField;
+use k256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar, U256};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurveError {
+    InvalidHex(String),
+    InvalidPoint(String),
+    InvalidScalar(String),
+}
+
+impl Display for CurveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CurveError::InvalidHex(s) => write!(f, "Invalid hex string {}", s),
+            CurveError::InvalidPoint(s) => write!(f, "Invalid curve point {}", s),
+            CurveError::InvalidScalar(s) => write!(f, "Invalid scalar {}", s),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CurveError {}
+
+/// Decodes a hex string, with or without `0x` prefix.
+pub fn decode_hex(input: &str) -> Result<Vec<u8>, CurveError> {
+    let value = input.strip_prefix("0x").unwrap_or(input);
+    hex::decode(value).map_err(|_| CurveError::InvalidHex(input.to_string()))
+}
+
+/// Parses a SEC1 encoded point (compressed or uncompressed) given in hex.
+pub fn point_from_hex(input: &str) -> Result<ProjectivePoint, CurveError> {
+    let bytes = decode_hex(input)?;
+    point_from_bytes(&bytes).ok_or_else(|| CurveError::InvalidPoint(input.to_string()))
+}
+
+/// Parses a SEC1 encoded point, rejecting the identity and points that are not on the curve.
+pub fn point_from_bytes(bytes: &[u8]) -> Option<ProjectivePoint> {
+    let encoded = EncodedPoint::from_bytes(bytes).ok()?;
+    let affine: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded).into();
+    affine.map(ProjectivePoint::from)
+}
+
+/// Builds a point from its affine coordinates given as 32 bytes big-endian hex.
+pub fn point_from_coordinates(x: &str, y: &str) -> Result<ProjectivePoint, CurveError> {
+    let x_bytes = field_bytes(x).ok_or_else(|| CurveError::InvalidPoint(x.to_string()))?;
+    let y_bytes = field_bytes(y).ok_or_else(|| CurveError::InvalidPoint(y.to_string()))?;
+    let encoded = EncodedPoint::from_affine_coordinates(&x_bytes, &y_bytes, false);
+    let affine: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded).into();
+    affine
+        .map(ProjectivePoint::from)
+        .ok_or_else(|| CurveError::InvalidPoint(alloc::format!("({}, {})", x, y)))
+}
+
+/// Compressed SEC1 encoding of a point (a single zero byte for the identity).
+pub fn point_to_bytes(point: &ProjectivePoint) -> Vec<u8> {
+    point.to_affine().to_encoded_point(true).as_bytes().to_vec()
+}
+
+pub fn point_to_hex(point: &ProjectivePoint) -> String {
+    hex::encode(point_to_bytes(point))
+}
+
+/// Ethereum address of a public key, the last 20 bytes of the keccak of its uncompressed encoding.
+pub fn point_to_address(point: &ProjectivePoint) -> [u8; 20] {
+    let encoded = point.to_affine().to_encoded_point(false);
+    let hash = keccak256(&encoded.as_bytes()[1..]);
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+/// Parses a canonical scalar (strictly lower than the group order) given in hex.
+pub fn scalar_from_hex(input: &str) -> Result<Scalar, CurveError> {
+    let bytes = field_bytes(input).ok_or_else(|| CurveError::InvalidScalar(input.to_string()))?;
+    Option::from(Scalar::from_repr(bytes))
+        .ok_or_else(|| CurveError::InvalidScalar(input.to_string()))
+}
+
+pub fn scalar_to_hex(scalar: &Scalar) -> String {
+    hex::encode(scalar.to_bytes())
+}
+
+/// `H_s`: Keccak-256 of the data, reduced modulo the group order.
+pub fn hash_to_scalar(data: &[u8]) -> Scalar {
+    <Scalar as Reduce<U256>>::reduce_bytes(&FieldBytes::from(keccak256(data)))
+}
+
+/// `H_p`: maps data to a curve point with unknown discrete logarithm (try-and-increment).
+///
+/// The x coordinate is `keccak256(data || counter)` with a 4 bytes big-endian counter starting at
+/// 0, the first candidate that lies on the curve is taken with an even y coordinate.
+pub fn hash_to_point(data: &[u8]) -> ProjectivePoint {
+    let mut candidate = [0u8; 33];
+    candidate[0] = 0x02;
+    let mut preimage = data.to_vec();
+    preimage.extend_from_slice(&[0u8; 4]);
+    let counter_at = preimage.len() - 4;
+    for counter in 0u32.. {
+        preimage[counter_at..].copy_from_slice(&counter.to_be_bytes());
+        candidate[1..].copy_from_slice(&keccak256(&preimage));
+        if let Some(point) = point_from_bytes(&candidate) {
+            return point;
+        }
+    }
+    unreachable!("hash_to_point exhausted its counter")
+}
+
+/// Left pads a hex value of at most 32 bytes.
+fn field_bytes(input: &str) -> Option<FieldBytes> {
+    let bytes = decode_hex(input).ok()?;
+    if bytes.len() > 32 {
+        return None;
+    }
+    let mut padded = [0u8; 32];
+    padded[32 - bytes.len()..].copy_from_slice(&bytes);
+    Some(FieldBytes::from(padded))
+}
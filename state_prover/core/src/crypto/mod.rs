@@ -0,0 +1,10 @@
+pub mod curve;
+pub use curve::*;
+pub mod pedersen;
+pub use pedersen::*;
+pub mod balance;
+pub use balance::*;
+pub mod ring_signature;
+pub use ring_signature::*;
+pub mod bulletproof;
+pub use bulletproof::*;
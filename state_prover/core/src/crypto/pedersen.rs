@@ -0,0 +1,125 @@
+//! Pedersen commitments to amounts.
+//!
+//! An amount `v` with blinding factor `gamma` commits to `C = gamma * G + v * H`, with
+//! `H = H_p(G)` so that nobody knows its discrete logarithm with respect to `G`. Clear amounts
+//! (coinbase outputs) use a zero blinding factor, anyone can then recompute `C = v * H`.
+//!
+//! Amounts are counted in units of `AMOUNT_UNIT_WEI` wei (gwei), so that every amount the
+//! bridge accepts fits the 64 bits of the range proofs: deposits are converted with
+//! `amount_from_wei` and withdrawals are paid `amount * AMOUNT_UNIT_WEI` wei.
+use crate::crypto::{hash_to_point, point_from_hex, point_to_bytes, point_to_hex};
+use alloc::string::{String, ToString};
+use alloy_primitives::U256;
+use core::fmt::Display;
+use k256::{ProjectivePoint, Scalar};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitmentError {
+    InvalidAmount(String),
+    InvalidCommitment(String),
+    Mismatch(String, String), // commitment, amount
+    FractionalAmount(String), // wei amount that is not a whole number of units
+    AmountOverflow(String),   // wei amount of 2^64 units or more
+}
+
+impl Display for CommitmentError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            CommitmentError::InvalidAmount(amount) => write!(f, "Invalid clear amount {}", amount),
+            CommitmentError::InvalidCommitment(commitment) => {
+                write!(f, "Invalid commitment {}", commitment)
+            }
+            CommitmentError::Mismatch(commitment, amount) => write!(
+                f,
+                "Commitment {} does not open to amount {}",
+                commitment, amount
+            ),
+            CommitmentError::FractionalAmount(wei) => write!(
+                f,
+                "Amount of {} wei is not a multiple of {} wei",
+                wei, AMOUNT_UNIT_WEI
+            ),
+            CommitmentError::AmountOverflow(wei) => {
+                write!(f, "Amount of {} wei does not fit 64 bits of units", wei)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CommitmentError {}
+
+/// Wei in a unit of the amounts of the UTXOs.
+pub const AMOUNT_UNIT_WEI: u64 = 1_000_000_000;
+
+/// Value generator `H` of the Pedersen commitments.
+pub fn generator_h() -> ProjectivePoint {
+    hash_to_point(&point_to_bytes(&ProjectivePoint::GENERATOR))
+}
+
+/// `gamma * G + amount * H`.
+pub fn commit(amount: u64, blinding: &Scalar) -> ProjectivePoint {
+    ProjectivePoint::GENERATOR * blinding + generator_h() * Scalar::from(amount)
+}
+
+/// Parses a clear amount, a base 10 integer of units fitting the range proofs (64 bits).
+pub fn parse_amount(amount: &str) -> Result<u64, CommitmentError> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(CommitmentError::InvalidAmount(amount.to_string()));
+    }
+    amount
+        .parse::<u64>()
+        .map_err(|_| CommitmentError::InvalidAmount(amount.to_string()))
+}
+
+/// Converts an amount in wei, as deposited on the root chain, to units.
+///
+/// The bridge must only accept deposits that are whole numbers of units below `2^64` units,
+/// anything else is rejected here rather than rounded.
+pub fn amount_from_wei(wei: &str) -> Result<u64, CommitmentError> {
+    if wei.is_empty() || !wei.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(CommitmentError::InvalidAmount(wei.to_string()));
+    }
+    let value = U256::from_str_radix(wei, 10)
+        .map_err(|_| CommitmentError::AmountOverflow(wei.to_string()))?;
+    let (units, rest) = value.div_rem(U256::from(AMOUNT_UNIT_WEI));
+    if rest != U256::ZERO {
+        return Err(CommitmentError::FractionalAmount(wei.to_string()));
+    }
+    u64::try_from(units).map_err(|_| CommitmentError::AmountOverflow(wei.to_string()))
+}
+
+/// Amount in wei of an amount in units.
+pub fn amount_to_wei(amount: u64) -> U256 {
+    U256::from(amount) * U256::from(AMOUNT_UNIT_WEI)
+}
+
+/// Commitment to a clear amount (zero blinding factor), as compressed point hex.
+///
+/// A zero amount is rejected as its commitment would be the identity.
+pub fn commit_clear_amount(amount: &str) -> Result<String, CommitmentError> {
+    let value = clear_amount(amount)?;
+    Ok(point_to_hex(&commit(value, &Scalar::ZERO)))
+}
+
+/// Checks that `commitment` opens to the clear `amount` with a zero blinding factor.
+pub fn verify_clear_commitment(commitment: &str, amount: &str) -> Result<(), CommitmentError> {
+    let value = clear_amount(amount)?;
+    let point = point_from_hex(commitment)
+        .map_err(|_| CommitmentError::InvalidCommitment(commitment.to_string()))?;
+    if point == commit(value, &Scalar::ZERO) {
+        Ok(())
+    } else {
+        Err(CommitmentError::Mismatch(
+            commitment.to_string(),
+            amount.to_string(),
+        ))
+    }
+}
+
+fn clear_amount(amount: &str) -> Result<u64, CommitmentError> {
+    match parse_amount(amount)? {
+        0 => Err(CommitmentError::InvalidAmount(amount.to_string())),
+        value => Ok(value),
+    }
+}
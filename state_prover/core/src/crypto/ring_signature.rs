@@ -0,0 +1,249 @@
+//! MLSAG ring signature verification.
+//!
+//! A signature over `n` ring members with `m` keys each carries `n * m` ring points stored row by
+//! row, `n` rows of `m` responses, the initial challenge `c_0` and one key image `I_j` for each
+//! of the first `k <= m` columns, the linkable ones. For every row `i` and column `j`:
+//!
+//! ```text
+//! L_ij = r_ij * G + c_i * P_ij
+//! R_ij = r_ij * H_p(P_ij) + c_i * I_j                       (linkable columns only)
+//! c_(i+1) = H_s(keccak256(message) || L_i0 || R_i0 || ... || L_i(k-1) || R_i(k-1)
+//!                                  || L_ik || ... || L_i(m-1))
+//! ```
+//!
+//! with points in compressed SEC1 form. The signature is valid when `c_n == c_0`.
+//!
+//! The ring of a RingCT transaction has `RING_CT_COLUMNS` columns, built by the verifier from the
+//! UTXOs of the inputs: the public key of the member, linkable, then its commitment minus the
+//! pseudo commitment of the transaction. Signing proves the knowledge of the secret key of one
+//! member and of the blinding factor difference `z` in `C_member - C_pseudo = z * G` on the same
+//! row, so the pseudo commitment hides the amount of the spent UTXO and nothing more. A single
+//! pseudo commitment is shared by all the rows: the rows of the decoys are not bound to anything,
+//! a pseudo commitment of their own would let the signer pick any amount for it.
+use crate::crypto::{
+    decode_hex, hash_to_point, hash_to_scalar, point_from_bytes, point_from_hex, point_to_bytes,
+    scalar_from_hex,
+};
+use crate::interface::{PendingRingCT, MLSAG, UTXO};
+use crate::keccak256;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use k256::ProjectivePoint;
+
+/// Columns of the ring of a RingCT transaction: public key, then commitment minus pseudo
+/// commitment. Only the first one has a key image.
+pub const RING_CT_COLUMNS: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MlsagError {
+    EmptyRing,
+    RingShape(usize, usize), // number of ring points, number of columns
+    ResponsesShape(usize),   // ring row
+    ColumnCount(usize),      // columns of the signature
+    KeyImageCount(usize),    // key images of the signature
+    PseudoCommitmentCount(usize),
+    InvalidPseudoCommitment,
+    InvalidRingPoint(usize, usize), // ring row, column
+    InvalidResponse(usize, usize),  // ring row, column
+    InvalidKeyImage(usize),         // column
+    InvalidChallenge,
+    InvalidEncoding,
+    MessageMismatch,
+    RingMismatch(usize),  // ring row
+    RingNotClosed(usize), // last ring row, whose challenge is not c_0
+}
+
+impl Display for MlsagError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            MlsagError::EmptyRing => write!(f, "Empty ring or no key image"),
+            MlsagError::RingShape(points, columns) => write!(
+                f,
+                "Ring of {} points does not fit rows of {} columns",
+                points, columns
+            ),
+            MlsagError::ResponsesShape(row) => {
+                write!(f, "Wrong number of responses for row {}", row)
+            }
+            MlsagError::ColumnCount(columns) => write!(
+                f,
+                "Signature has {} columns, expected {}",
+                columns, RING_CT_COLUMNS
+            ),
+            MlsagError::KeyImageCount(key_images) => {
+                write!(f, "Signature has {} key images, expected 1", key_images)
+            }
+            MlsagError::PseudoCommitmentCount(count) => write!(
+                f,
+                "Transaction has {} pseudo commitments, expected 1",
+                count
+            ),
+            MlsagError::InvalidPseudoCommitment => write!(f, "Invalid pseudo commitment"),
+            MlsagError::InvalidRingPoint(row, column) => {
+                write!(f, "Invalid ring point at row {} column {}", row, column)
+            }
+            MlsagError::InvalidResponse(row, column) => {
+                write!(f, "Invalid response at row {} column {}", row, column)
+            }
+            MlsagError::InvalidKeyImage(column) => write!(f, "Invalid key image {}", column),
+            MlsagError::InvalidChallenge => write!(f, "Invalid challenge"),
+            MlsagError::InvalidEncoding => write!(f, "Signature cannot be decoded"),
+            MlsagError::MessageMismatch => {
+                write!(f, "Signature does not sign the transaction hash")
+            }
+            MlsagError::RingMismatch(row) => {
+                write!(f, "Ring row {} does not match the UTXO of its input", row)
+            }
+            MlsagError::RingNotClosed(row) => {
+                write!(
+                    f,
+                    "Ring does not close, challenge after row {} is not c_0",
+                    row
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MlsagError {}
+
+impl MLSAG {
+    /// Number of columns of the rows, checking that every row has as many responses.
+    fn columns(&self) -> Result<usize, MlsagError> {
+        let columns = match self.responses.first() {
+            Some(row) if !row.is_empty() && !self.key_images.is_empty() => row.len(),
+            _ => return Err(MlsagError::EmptyRing),
+        };
+        if let Some(row) = self.responses.iter().position(|row| row.len() != columns) {
+            return Err(MlsagError::ResponsesShape(row));
+        }
+        if self.key_images.len() > columns {
+            return Err(MlsagError::InvalidKeyImage(columns));
+        }
+        Ok(columns)
+    }
+
+    /// Verifies the ring signature over its own message and its own ring.
+    pub fn verify(&self) -> Result<(), MlsagError> {
+        let columns = self.columns()?;
+        let mut ring = Vec::with_capacity(self.ring.len());
+        for (index, point) in self.ring.iter().enumerate() {
+            let point = point
+                .to_point()
+                .map_err(|_| MlsagError::InvalidRingPoint(index / columns, index % columns))?;
+            ring.push(point);
+        }
+        self.verify_ring(&ring)
+    }
+
+    /// Verifies the ring signature over its own message and `ring`, given row by row, whatever
+    /// ring the signature carries.
+    pub fn verify_ring(&self, ring: &[ProjectivePoint]) -> Result<(), MlsagError> {
+        let columns = self.columns()?;
+        let rows = self.responses.len();
+        if ring.len() != rows * columns {
+            return Err(MlsagError::RingShape(ring.len(), columns));
+        }
+
+        let mut key_images = Vec::with_capacity(self.key_images.len());
+        for (column, key_image) in self.key_images.iter().enumerate() {
+            let point = decode_hex(key_image)
+                .ok()
+                .and_then(|bytes| point_from_bytes(&bytes))
+                .filter(|point| *point != ProjectivePoint::IDENTITY)
+                .ok_or(MlsagError::InvalidKeyImage(column))?;
+            key_images.push(point);
+        }
+        let c_0 = scalar_from_hex(&self.c).map_err(|_| MlsagError::InvalidChallenge)?;
+        let message = keccak256(self.message.as_bytes());
+
+        let mut c = c_0;
+        for row in 0..rows {
+            let mut transcript = message.to_vec();
+            for column in 0..columns {
+                let point = ring[row * columns + column];
+                let response = scalar_from_hex(&self.responses[row][column])
+                    .map_err(|_| MlsagError::InvalidResponse(row, column))?;
+                let l = ProjectivePoint::GENERATOR * response + point * c;
+                transcript.extend(point_to_bytes(&l));
+                if let Some(key_image) = key_images.get(column) {
+                    // a key image of the identity would not link anything
+                    if point == ProjectivePoint::IDENTITY {
+                        return Err(MlsagError::InvalidRingPoint(row, column));
+                    }
+                    let r = hash_to_point(&point_to_bytes(&point)) * response + *key_image * c;
+                    transcript.extend(point_to_bytes(&r));
+                }
+            }
+            c = hash_to_scalar(&transcript);
+        }
+
+        if c == c_0 {
+            Ok(())
+        } else {
+            Err(MlsagError::RingNotClosed(rows - 1))
+        }
+    }
+}
+
+/// Verifies the signature of a RingCT transaction against the UTXOs referenced by its inputs.
+///
+/// `signature` is the hex encoding of the bincode serialized `MLSAG`, it must sign the
+/// transaction hash with one row per input and `RING_CT_COLUMNS` columns. The ring is built
+/// from the public keys and commitments of `inputs` and the pseudo commitment of the
+/// transaction, and the ring carried by the signature must be the same.
+pub fn verify_ring_signature(tx: &PendingRingCT, inputs: &[&UTXO]) -> Result<MLSAG, MlsagError> {
+    let signature = decode_signature(tx)?;
+    if signature.message != tx.hash {
+        return Err(MlsagError::MessageMismatch);
+    }
+    let columns = signature.columns()?;
+    if columns != RING_CT_COLUMNS {
+        return Err(MlsagError::ColumnCount(columns));
+    }
+    if signature.key_images.len() != 1 {
+        return Err(MlsagError::KeyImageCount(signature.key_images.len()));
+    }
+    if inputs.is_empty() || signature.responses.len() != inputs.len() {
+        return Err(MlsagError::RingShape(signature.ring.len(), columns));
+    }
+    let pseudo_commitment = match tx.pseudo_commitments.as_slice() {
+        [commitment] => {
+            point_from_hex(commitment).map_err(|_| MlsagError::InvalidPseudoCommitment)?
+        }
+        commitments => return Err(MlsagError::PseudoCommitmentCount(commitments.len())),
+    };
+
+    let mut ring = Vec::with_capacity(inputs.len() * RING_CT_COLUMNS);
+    for (row, input) in inputs.iter().enumerate() {
+        let public_key =
+            point_from_hex(&input.get_public_key()).map_err(|_| MlsagError::RingMismatch(row))?;
+        let commitment =
+            point_from_hex(&input.get_commitment()).map_err(|_| MlsagError::RingMismatch(row))?;
+        ring.push(public_key);
+        ring.push(commitment - pseudo_commitment);
+    }
+    if signature.ring.len() != ring.len() {
+        return Err(MlsagError::RingShape(signature.ring.len(), columns));
+    }
+    for (index, (point, expected)) in signature.ring.iter().zip(&ring).enumerate() {
+        let (row, column) = (index / columns, index % columns);
+        let point = point
+            .to_point()
+            .map_err(|_| MlsagError::InvalidRingPoint(row, column))?;
+        if point != *expected {
+            return Err(MlsagError::RingMismatch(row));
+        }
+    }
+    signature.verify_ring(&ring)?;
+    Ok(signature)
+}
+
+/// Decodes the signature of a transaction without verifying it.
+pub fn decode_signature(tx: &PendingRingCT) -> Result<MLSAG, MlsagError> {
+    decode_hex(&tx.signature)
+        .ok()
+        .and_then(|bytes| MLSAG::decode(&bytes))
+        .ok_or(MlsagError::InvalidEncoding)
+}
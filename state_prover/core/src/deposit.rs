@@ -0,0 +1,56 @@
+//! Deposits applied by a batch.
+//!
+//! A deposit mints a coinbase output for funds locked in the bridge on the root chain, so every
+//! deposit of the batch is committed in the journal: `depositHash` is
+//! `keccak256(abi.encode(deposits))` of the `Deposit[]` in the order they are applied, zero for a
+//! batch without deposit. The bridge rebuilds it from the deposits it received and rejects a batch
+//! minting anything else. `txId` is the hash of the deposit transaction on the root chain, the
+//! public key the one-time key of the coinbase output and the amount in wei, the clear amount of
+//! the output times `AMOUNT_UNIT_WEI`. A `txId` is only applied once, the state keeps every
+//! applied one, see `State`.
+use crate::crypto::{amount_to_wei, parse_amount, verify_clear_commitment};
+use crate::interface::{
+    compressed_point, hash_bytes32, CompressedPoint, DepositError, PendingUserDepositTx, UTXO,
+};
+use crate::keccak256;
+use crate::merkle::Hash;
+use alloy_sol_types::{sol, SolValue};
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Deposit {
+        bytes32 txId;
+        CompressedPoint publicKey;
+        uint256 amount;
+        string currency;
+    }
+}
+
+impl Deposit {
+    /// Deposit of a deposit transaction, whose output must be a coinbase committing to its clear
+    /// amount.
+    pub fn from_deposit_tx(tx: &PendingUserDepositTx) -> Result<Self, DepositError> {
+        let output = match &tx.output {
+            UTXO::Coinbase(output) => output,
+            output => return Err(DepositError::InvalidOutput(output.get_hash())),
+        };
+        verify_clear_commitment(&output.commitment, &output.amount)
+            .map_err(DepositError::InvalidAmount)?;
+        let amount = parse_amount(&output.amount).map_err(DepositError::InvalidAmount)?;
+        Ok(Deposit {
+            txId: hash_bytes32("txId", &tx.txId).map_err(DepositError::InvalidEncoding)?,
+            publicKey: compressed_point("public_key", &output.public_key)
+                .map_err(DepositError::InvalidEncoding)?,
+            amount: amount_to_wei(amount),
+            currency: output.currency.clone(),
+        })
+    }
+}
+
+/// Hash of the deposits of a batch, zero for a batch without deposit.
+pub fn deposit_hash(deposits: &[Deposit]) -> Hash {
+    if deposits.is_empty() {
+        return [0u8; 32];
+    }
+    keccak256(&deposits.abi_encode())
+}
@@ -0,0 +1,10 @@
+use tiny_keccak::{Hasher, Keccak};
+
+/// Computes the Keccak-256 digest of the given bytes.
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(bytes);
+    hasher.finalize(&mut digest);
+    digest
+}
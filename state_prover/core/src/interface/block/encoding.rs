@@ -0,0 +1,77 @@
+//! Canonical encoding of the block headers, the preimage of the block hash, and of the ordered
+//! transactions of a block, the preimage of its transaction root.
+//!
+//! Same scheme as the UTXOs and the transactions: `keccak256(abi.encode(encoding))` with the hash
+//! of a tag in the first word. Hashes and roots are `bytes32`, so that a `0x` prefix or the case
+//! of the hex string does not change the hash of the block.
+use crate::interface::{BlockError, BlockHeader, PendingTransaction};
+use crate::keccak256;
+use crate::merkle::parse_hash;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{sol, SolValue};
+use core::str::FromStr;
+
+pub const BLOCK_HEADER_DOMAIN: &str = "CypherZer0/BlockHeader/v1";
+pub const BLOCK_TRANSACTIONS_DOMAIN: &str = "CypherZer0/BlockTransactions/v1";
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct BlockHeaderEncoding {
+        bytes32 domain;
+        uint64 height;
+        bytes32 parentHash;
+        uint64 timestamp;
+        bytes32 txRoot;
+        bytes32 preUtxoRoot;
+        bytes32 postUtxoRoot;
+        address sequencer;
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct BlockTransactionsEncoding {
+        bytes32 domain;
+        bytes32[] transactions; // hashes, in the order the transactions are applied
+    }
+}
+
+fn bytes32(hash: &str) -> Result<B256, BlockError> {
+    parse_hash(hash)
+        .map(B256::from)
+        .map_err(|_| BlockError::InvalidHash(hash.to_string()))
+}
+
+impl BlockHeader {
+    pub fn encoding(&self) -> Result<BlockHeaderEncoding, BlockError> {
+        Ok(BlockHeaderEncoding {
+            domain: B256::from(keccak256(BLOCK_HEADER_DOMAIN.as_bytes())),
+            height: self.height,
+            parentHash: bytes32(&self.parent_hash)?,
+            timestamp: self.timestamp,
+            txRoot: bytes32(&self.tx_root)?,
+            preUtxoRoot: bytes32(&self.pre_utxo_root)?,
+            postUtxoRoot: bytes32(&self.post_utxo_root)?,
+            sequencer: Address::from_str(&self.sequencer)
+                .map_err(|_| BlockError::InvalidSequencer(self.sequencer.clone()))?,
+        })
+    }
+
+    /// Hash of the block, hex encoded without prefix.
+    pub fn block_hash(&self) -> Result<String, BlockError> {
+        Ok(hex::encode(keccak256(&self.encoding()?.abi_encode())))
+    }
+}
+
+impl BlockTransactionsEncoding {
+    pub fn new(transactions: &[PendingTransaction]) -> Result<Self, BlockError> {
+        Ok(BlockTransactionsEncoding {
+            domain: B256::from(keccak256(BLOCK_TRANSACTIONS_DOMAIN.as_bytes())),
+            transactions: transactions
+                .iter()
+                .map(|tx| parse_hash(&tx.get_hash()).map(B256::from))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(BlockError::InvalidTransactions)?,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use crate::interface::{BlockTransactionsEncoding, PendingTransaction};
+use crate::keccak256;
+use crate::merkle::{parse_hash, MerkleError};
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloy_sol_types::SolValue;
+use core::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockError {
+    InvalidHash(String),
+    InvalidSequencer(String),
+    InvalidTransactions(MerkleError),
+    TxRootMismatch(String, String), // header, computed
+}
+
+impl Display for BlockError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            BlockError::InvalidHash(hash) => write!(f, "Header hash {} is not 32 bytes hex", hash),
+            BlockError::InvalidSequencer(sequencer) => {
+                write!(f, "Sequencer {} is not an address", sequencer)
+            }
+            BlockError::InvalidTransactions(e) => write!(f, "Invalid transaction hash: {}", e),
+            BlockError::TxRootMismatch(header, computed) => write!(
+                f,
+                "Transaction root {} does not match the transactions, expected {}",
+                header, computed
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlockError {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub height: u64,            // number of the block, the first block is 1
+    pub parent_hash: String,    // hash of the previous block, zero for the first block
+    pub timestamp: u64,         // unix time in seconds, set by the sequencer
+    pub tx_root: String,        // hash of the ordered hashes of the transactions
+    pub pre_utxo_root: String,  // root of the UTXO set before the transactions
+    pub post_utxo_root: String, // root of the UTXO set after the transactions
+    pub sequencer: String,      // address of the sequencer that built the block
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Block {
+    pub header: BlockHeader,
+    pub transactions: Vec<PendingTransaction>, // applied in order
+}
+
+/// Root of the transactions of a block, `keccak256(abi.encode(BlockTransactionsEncoding))` over
+/// their hashes in order, zero for a block without transaction. The transactions are applied in
+/// that order, so any other order gives another root.
+pub fn transactions_root(transactions: &[PendingTransaction]) -> Result<String, BlockError> {
+    if transactions.is_empty() {
+        return Ok(hex::encode([0u8; 32]));
+    }
+    let encoding = BlockTransactionsEncoding::new(transactions)?;
+    Ok(hex::encode(keccak256(&encoding.abi_encode())))
+}
+
+impl Block {
+    /// Hash of the header, which commits to the transactions through their root.
+    pub fn hash(&self) -> Result<String, BlockError> {
+        self.header.block_hash()
+    }
+
+    /// Checks the transaction root of the header against the transactions of the block.
+    pub fn verify_tx_root(&self) -> Result<(), BlockError> {
+        let computed = transactions_root(&self.transactions)?;
+        let tx_root = parse_hash(&self.header.tx_root)
+            .map_err(|_| BlockError::InvalidHash(self.header.tx_root.clone()))?;
+        if hex::encode(tx_root) != computed {
+            return Err(BlockError::TxRootMismatch(
+                self.header.tx_root.clone(),
+                computed,
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+mod encoding;
+pub use encoding::*;
+mod header;
+pub use header::*;
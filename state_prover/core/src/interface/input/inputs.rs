@@ -0,0 +1,40 @@
+use crate::interface::Block;
+use crate::merkle::{Hash, MerkleAccumulator};
+use crate::state::{BlockWitness, StateCommitment};
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Inputs {
+    pub state_t_1: String, // root of the block accumulator at t+1 (all the blocks)
+    pub state_t: String,   // root of the block accumulator at t (all the blocks)
+    pub block_frontier: MerkleAccumulator, // frontier of the block accumulator at t
+    pub parent_proof: Vec<Hash>, // proof that the parent of the first block is the last block at t
+    pub blocks: Vec<Block>, // blocks of the batch, their hashes are appended to the accumulator
+    pub state: StateCommitment, // roots of the UTXO set and of the spent key images at t
+    pub witnesses: Vec<BlockWitness>, // updates of the state trees, one per block
+}
+
+impl Inputs {
+    /// Number of the first block of the batch, `None` for an empty batch.
+    pub fn from_block(&self) -> Option<u64> {
+        self.blocks.first().map(|block| block.header.height)
+    }
+
+    /// Number of the last block of the batch, `None` for an empty batch.
+    pub fn to_block(&self) -> Option<u64> {
+        self.blocks.last().map(|block| block.header.height)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Inputs {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        bincode::deserialize(bytes).unwrap()
+    }
+}
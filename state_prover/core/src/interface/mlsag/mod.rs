@@ -0,0 +1,4 @@
+pub mod point;
+pub mod signature;
+pub use point::Point;
+pub use signature::MLSAG;
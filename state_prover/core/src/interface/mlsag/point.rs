@@ -0,0 +1,56 @@
+use crate::crypto::{point_from_coordinates, CurveError};
+use alloc::string::String;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::ProjectivePoint;
+use serde::{Deserialize, Serialize};
+
+/// Affine coordinates of a point, `(0, 0)` standing for the identity as it is not on the curve.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Point {
+    x: String,
+    y: String,
+}
+
+impl Point {
+    /// Point of the given affine coordinates, hex encoded, as they are.
+    pub fn new(x: String, y: String) -> Point {
+        Point { x, y }
+    }
+
+    pub fn from_point(point: &ProjectivePoint) -> Point {
+        let encoded = point.to_affine().to_encoded_point(false);
+        match (encoded.x(), encoded.y()) {
+            (Some(x), Some(y)) => Point {
+                x: hex::encode(x),
+                y: hex::encode(y),
+            },
+            _ => Point {
+                x: hex::encode([0u8; 32]),
+                y: hex::encode([0u8; 32]),
+            },
+        }
+    }
+
+    /// Decodes the affine coordinates, given as big-endian hex, into a curve point.
+    pub fn to_point(&self) -> Result<ProjectivePoint, CurveError> {
+        let zero = hex::encode([0u8; 32]);
+        let is_zero = |value: &str| value.strip_prefix("0x").unwrap_or(value) == zero;
+        if is_zero(&self.x) && is_zero(&self.y) {
+            return Ok(ProjectivePoint::IDENTITY);
+        }
+        point_from_coordinates(&self.x, &self.y)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Point {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let encoded = bincode::serialize(&self)?;
+        Ok(encoded)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Point, Box<dyn std::error::Error>> {
+        let point: Point = bincode::deserialize(bytes)?;
+        Ok(point)
+    }
+}
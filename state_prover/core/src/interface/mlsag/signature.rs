@@ -0,0 +1,75 @@
+use crate::interface::Point;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct MLSAG {
+    pub message: String,
+    pub ring: Vec<Point>,
+    pub c: String,
+    pub responses: Vec<Vec<String>>,
+    pub key_images: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+impl MLSAG {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let encoded = bincode::serialize(&self)?;
+        Ok(encoded)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<MLSAG, Box<dyn std::error::Error>> {
+        let mlsag: MLSAG = bincode::deserialize(bytes)?;
+        Ok(mlsag)
+    }
+}
+
+impl MLSAG {
+    /// Decodes the bincode encoding written by `to_bytes`, without bincode so that the guest can
+    /// read signatures without `std`. Lengths are `u64` little endian, strings their UTF-8 bytes
+    /// and the fields come in declaration order. Unlike `from_bytes`, trailing bytes are rejected.
+    pub fn decode(bytes: &[u8]) -> Option<MLSAG> {
+        let mut reader = Reader(bytes);
+        let mlsag = MLSAG {
+            message: reader.string()?,
+            ring: reader.vec(|r| Some(Point::new(r.string()?, r.string()?)))?,
+            c: reader.string()?,
+            responses: reader.vec(|r| r.vec(Reader::string))?,
+            key_images: reader.vec(Reader::string)?,
+        };
+        reader.0.is_empty().then_some(mlsag)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.0.len() {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(taken)
+    }
+
+    fn len(&mut self) -> Option<usize> {
+        let bytes = self.take(8)?.try_into().ok()?;
+        usize::try_from(u64::from_le_bytes(bytes)).ok()
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let len = self.len()?;
+        String::from_utf8(self.take(len)?.to_vec()).ok()
+    }
+
+    fn vec<T>(&mut self, mut item: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let len = self.len()?;
+        // every item takes 8 bytes at least, a longer count cannot be read, nor allocated
+        if len > self.0.len() / 8 {
+            return None;
+        }
+        (0..len).map(|_| item(self)).collect()
+    }
+}
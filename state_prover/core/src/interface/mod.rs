@@ -1,7 +1,11 @@
 pub mod range_proof;
 pub use range_proof::*;
+pub mod block;
+pub use block::*;
 pub mod input;
 pub use input::*;
+pub mod mlsag;
+pub use mlsag::*;
 pub mod utxo;
 pub use utxo::*;
 pub mod pending_transactions;
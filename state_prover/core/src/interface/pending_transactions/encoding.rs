@@ -0,0 +1,103 @@
+//! Canonical encoding of the pending transactions, the preimage of their hash.
+//!
+//! Same scheme as the UTXOs: `keccak256(abi.encode(encoding))` with a tag unique to the variant
+//! in the first word. Outputs are committed to through their canonical UTXO hash, so the hash of
+//! a transaction, which its ring signature signs, covers the content of every output. The
+//! signature itself is not part of the hash. Inputs and deposit ids are `bytes32` and pseudo
+//! commitments `CompressedPoint`s, decoded as the fields of the UTXOs are.
+use crate::interface::{
+    compressed_point, hash_bytes32, CompressedPoint, EncodingError, PendingRingCT,
+    PendingTransaction, PendingUserDepositTx, UTXO,
+};
+use crate::keccak256;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use alloy_sol_types::{sol, SolValue};
+
+pub const RING_CT_DOMAIN: &str = "CypherZer0/PendingRingCT/v1";
+pub const USER_DEPOSIT_DOMAIN: &str = "CypherZer0/PendingUserDepositTx/v1";
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct PendingRingCTEncoding {
+        bytes32 domain;
+        bytes32[] inputs;
+        bytes32[] outputs;
+        CompressedPoint[] pseudoCommitments;
+        uint64 fee;
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct PendingUserDepositTxEncoding {
+        bytes32 domain;
+        bytes32 txId;
+        bytes32 output;
+    }
+}
+
+fn output_hash(output: &UTXO) -> Result<B256, EncodingError> {
+    hash_bytes32("output", &output.utxo_hash()?)
+}
+
+impl PendingRingCT {
+    pub fn encoding(&self) -> Result<PendingRingCTEncoding, EncodingError> {
+        Ok(PendingRingCTEncoding {
+            domain: B256::from(keccak256(RING_CT_DOMAIN.as_bytes())),
+            inputs: self
+                .inputs
+                .iter()
+                .map(|input| hash_bytes32("input", input))
+                .collect::<Result<Vec<_>, _>>()?,
+            outputs: self
+                .outputs
+                .iter()
+                .map(output_hash)
+                .collect::<Result<Vec<_>, _>>()?,
+            pseudoCommitments: self
+                .pseudo_commitments
+                .iter()
+                .map(|commitment| compressed_point("pseudo_commitment", commitment))
+                .collect::<Result<Vec<_>, _>>()?,
+            fee: self.fee,
+        })
+    }
+
+    /// Hash of the content of the transaction, whatever its `hash` field holds.
+    pub fn tx_hash(&self) -> Result<String, EncodingError> {
+        Ok(hex::encode(keccak256(&self.encoding()?.abi_encode())))
+    }
+}
+
+impl PendingUserDepositTx {
+    pub fn encoding(&self) -> Result<PendingUserDepositTxEncoding, EncodingError> {
+        Ok(PendingUserDepositTxEncoding {
+            domain: B256::from(keccak256(USER_DEPOSIT_DOMAIN.as_bytes())),
+            txId: hash_bytes32("txId", &self.txId)?,
+            output: output_hash(&self.output)?,
+        })
+    }
+
+    /// Hash of the content of the transaction, whatever its `hash` field holds.
+    pub fn tx_hash(&self) -> Result<String, EncodingError> {
+        Ok(hex::encode(keccak256(&self.encoding()?.abi_encode())))
+    }
+}
+
+impl PendingTransaction {
+    /// Canonical hash of the transaction, hex encoded without prefix.
+    pub fn tx_hash(&self) -> Result<String, EncodingError> {
+        match self {
+            PendingTransaction::PendingDeposit(deposit) => deposit.tx_hash(),
+            PendingTransaction::PendingRingCTx(ring) => ring.tx_hash(),
+        }
+    }
+
+    /// Outputs created by the transaction.
+    pub fn outputs(&self) -> &[UTXO] {
+        match self {
+            PendingTransaction::PendingDeposit(deposit) => core::slice::from_ref(&deposit.output),
+            PendingTransaction::PendingRingCTx(ring) => &ring.outputs,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod encoding;
+pub use encoding::*;
 pub mod pending_transaction;
 pub use pending_transaction::*;
 pub mod pending_user_deposit;
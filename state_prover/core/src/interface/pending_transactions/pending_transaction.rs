@@ -1,4 +1,7 @@
 use crate::interface::{PendingRingCT, PendingUserDepositTx};
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PendingTransaction {
@@ -7,6 +10,7 @@ pub enum PendingTransaction {
 }
 
 impl PendingTransaction {
+    #[cfg(feature = "std")]
     pub fn from_bytes(bytes: &[u8]) -> Result<PendingTransaction, Box<dyn std::error::Error>> {
         let tx: PendingTransaction = bincode::deserialize(bytes)?;
         Ok(tx)
@@ -17,6 +21,7 @@ impl PendingTransaction {
             PendingTransaction::PendingRingCTx(_) => "RingCT".to_string(),
         }
     }
+    #[cfg(feature = "std")]
     pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let encoded = bincode::serialize(&self)?;
         Ok(encoded)
@@ -0,0 +1,102 @@
+use crate::crypto::CommitmentError;
+use crate::interface::{EncodingError, UTXO};
+#[cfg(feature = "std")]
+use crate::{
+    crypto::{amount_from_wei, commit_clear_amount},
+    interface::CoinbaseUTXO,
+};
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepositError {
+    InvalidAmount(CommitmentError),
+    InvalidEncoding(EncodingError),
+    InvalidOutput(String), // hash of an output that is not a coinbase
+}
+
+impl Display for DepositError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            DepositError::InvalidAmount(e) => write!(f, "Invalid deposit amount: {}", e),
+            DepositError::InvalidEncoding(e) => write!(f, "Invalid deposit output: {}", e),
+            DepositError::InvalidOutput(output) => {
+                write!(f, "Deposit output {} is not a coinbase", output)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DepositError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDepositEvent {
+    pub txId: String,     // deposit tx hash from the network used to deposit the funds
+    pub amount: String,   // Amount deposited in wei
+    pub currency: String, // the currency
+    pub root_block_number: u64, // Root block number of the deposit
+    pub root_blockchain: String, //Ticker for the root blockchain
+    pub public_key: String, // Key image of the deposit
+    pub r_g: String,      // rG = G*r
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PendingUserDepositTx {
+    pub txId: String, // hash of the deposit transaction on the root chain
+    pub output: UTXO, // hash of the UTXO
+    pub hash: String, // hash of the transaction
+}
+
+impl PendingUserDepositTx {
+    /// Builds the deposit transaction, its output commits to the clear deposited amount converted
+    /// from wei to units.
+    #[cfg(feature = "std")]
+    pub fn from_user_deposit_event(
+        event: UserDepositEvent,
+    ) -> Result<PendingUserDepositTx, DepositError> {
+        let amount = amount_from_wei(&event.amount)
+            .map_err(DepositError::InvalidAmount)?
+            .to_string();
+        let commitment = commit_clear_amount(&amount).map_err(DepositError::InvalidAmount)?;
+        let output: UTXO = UTXO::Coinbase(
+            CoinbaseUTXO {
+                version: "0x01".to_string(),
+                transaction_hash: "00".repeat(32), // coinbase transactions have a zero hash
+                output_index: 0,                   // because only one output
+                public_key: event.public_key,
+                unlock_time: None,
+                amount,
+                currency: event.currency,
+                commitment,
+                rG: event.r_g,
+                hash: String::new(),
+            }
+            .hashed()
+            .map_err(DepositError::InvalidEncoding)?,
+        );
+        let mut tx = PendingUserDepositTx {
+            txId: event.txId,
+            output,
+            hash: String::new(),
+        };
+        tx.hash = tx.tx_hash().map_err(DepositError::InvalidEncoding)?;
+        Ok(tx)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let encoded = bincode::serialize(&self)?;
+        Ok(encoded)
+    }
+    #[cfg(feature = "std")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<PendingUserDepositTx, Box<dyn std::error::Error>> {
+        let tx: PendingUserDepositTx = bincode::deserialize(bytes)?;
+        Ok(tx)
+    }
+}
+
+//G*hash(clef publique view)*r(alaeatoire connu par envoyeur)+clef publique spend
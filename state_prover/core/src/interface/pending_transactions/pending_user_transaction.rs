@@ -1,14 +1,19 @@
 use crate::interface::UTXO;
+use alloc::string::String;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PendingRingCT {
-    pub inputs: Vec<String>, // Ethereum address of the depositor
+    pub inputs: Vec<String>, // hashes of the UTXOs of the ring, one per row
     pub outputs: Vec<UTXO>,
     pub hash: String,      // hash of the UTXO// hash of the transaction
     pub signature: String, // signature of the transaction in hex format
+    pub pseudo_commitments: Vec<String>, // (compressed point) -> re-blinded commitment of each spent input
+    pub fee: u64,                        // clear fee paid by the transaction
 }
 
+#[cfg(feature = "std")]
 impl PendingRingCT {
     pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let encoded = bincode::serialize(&self)?;
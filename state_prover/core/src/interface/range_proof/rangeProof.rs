@@ -1,4 +1,6 @@
-use risc0_zkvm::serde::{Serialize, Deserialize};
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
 pub struct LeftRigth {
@@ -38,6 +40,7 @@ pub struct RangeProof {
     pub order: String,
 }
 
+#[cfg(feature = "std")]
 impl LeftRigth {
     pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let encoded = bincode::serialize(&self)?;
@@ -50,6 +53,7 @@ impl LeftRigth {
     }
 }
 
+#[cfg(feature = "std")]
 impl LigthRangeProof {
     pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let encoded = bincode::serialize(&self)?;
@@ -62,6 +66,7 @@ impl LigthRangeProof {
     }
 }
 
+#[cfg(feature = "std")]
 impl RangeProof {
     pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
         let encoded = bincode::serialize(&self)?;
@@ -0,0 +1,285 @@
+//! Canonical encoding of the UTXOs, the preimage of their hash.
+//!
+//! The hash of a UTXO is `keccak256(abi.encode(encoding))` where `encoding` is the struct of its
+//! variant below, and the first word is the hash of a tag unique to the variant so that two
+//! variants never share a preimage. Every field is decoded before it is encoded, so the hash does
+//! not depend on how a client writes the hex strings:
+//! - points are `CompressedPoint { bytes1 prefix; bytes32 x; }`, the compressed SEC1 encoding
+//!   split in two words as Solidity has no `bytes33`, whatever encoding the UTXO carries;
+//! - scalars are their canonical 32 bytes, hashes `bytes32`, the version a `uint64`;
+//! - clear amounts are `uint256`, the encrypted amount of a payment is opaque and hashed as
+//!   the bytes of its hex;
+//! - the currency and the exit chain are names, hashed as UTF-8 strings.
+//!
+//! The same structs can be declared in Solidity, and any ABI encoder of a client gives the same
+//! bytes. A missing unlock time is encoded as `hasUnlockTime = false` and `unlockTime = 0`.
+use crate::crypto::{decode_hex, parse_amount, point_from_hex, point_to_bytes, scalar_from_hex};
+use crate::interface::range_proof::LigthRangeProof;
+use crate::interface::{CoinbaseUTXO, ExitUTXO, PaymentUTXO, UTXO};
+use crate::keccak256;
+use crate::merkle::parse_hash;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloy_primitives::{FixedBytes, B256, U256};
+use alloy_sol_types::{sol, SolValue};
+use core::fmt::Display;
+
+pub const PAYMENT_UTXO_DOMAIN: &str = "CypherZer0/PaymentUTXO/v1";
+pub const EXIT_UTXO_DOMAIN: &str = "CypherZer0/ExitUTXO/v1";
+pub const COINBASE_UTXO_DOMAIN: &str = "CypherZer0/CoinbaseUTXO/v1";
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct CompressedPoint {
+        bytes1 prefix; // 0x02 for an even y, 0x03 for an odd one
+        bytes32 x;
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct RangeProofEncoding {
+        CompressedPoint V;
+        CompressedPoint A;
+        CompressedPoint S;
+        CompressedPoint T1;
+        CompressedPoint T2;
+        bytes32 tx;
+        bytes32 txbf;
+        bytes32 e;
+        bytes32 a0;
+        bytes32 b0;
+        CompressedPoint[] L;
+        CompressedPoint[] R;
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct PaymentUTXOEncoding {
+        bytes32 domain;
+        uint64 version;
+        bytes32 transactionHash;
+        uint64 outputIndex;
+        CompressedPoint publicKey;
+        bool hasUnlockTime;
+        uint64 unlockTime;
+        bytes amount;
+        string currency;
+        CompressedPoint commitment;
+        bytes32 rangeProofHash; // keccak256(abi.encode(RangeProofEncoding))
+        CompressedPoint rG;
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct ExitUTXOEncoding {
+        bytes32 domain;
+        bytes32 transactionHash;
+        uint64 outputIndex;
+        CompressedPoint publicKey;
+        bool hasUnlockTime;
+        uint64 unlockTime;
+        uint256 amount;
+        string currency;
+        CompressedPoint commitment;
+        string exitChain;
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct CoinbaseUTXOEncoding {
+        bytes32 domain;
+        uint64 version;
+        bytes32 transactionHash;
+        uint64 outputIndex;
+        CompressedPoint publicKey;
+        bool hasUnlockTime;
+        uint64 unlockTime;
+        uint256 amount;
+        string currency;
+        CompressedPoint commitment;
+        CompressedPoint rG;
+    }
+}
+
+/// A field that does not decode to its canonical type, with the name of the field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    InvalidPoint(&'static str, String),
+    InvalidScalar(&'static str, String),
+    InvalidHex(&'static str, String),
+    InvalidNumber(&'static str, String),
+}
+
+impl Display for EncodingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            EncodingError::InvalidPoint(field, value) => {
+                write!(f, "Field {} is not a curve point: {}", field, value)
+            }
+            EncodingError::InvalidScalar(field, value) => {
+                write!(f, "Field {} is not a canonical scalar: {}", field, value)
+            }
+            EncodingError::InvalidHex(field, value) => {
+                write!(f, "Field {} is not valid hex: {}", field, value)
+            }
+            EncodingError::InvalidNumber(field, value) => {
+                write!(f, "Field {} is not a valid number: {}", field, value)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodingError {}
+
+/// Compressed encoding of a point given in any SEC1 encoding, the identity is rejected.
+pub fn compressed_point(
+    field: &'static str,
+    value: &str,
+) -> Result<CompressedPoint, EncodingError> {
+    let invalid = || EncodingError::InvalidPoint(field, value.to_string());
+    let bytes = point_to_bytes(&point_from_hex(value).map_err(|_| invalid())?);
+    if bytes.len() != 33 {
+        return Err(invalid());
+    }
+    Ok(CompressedPoint {
+        prefix: FixedBytes([bytes[0]]),
+        x: B256::from_slice(&bytes[1..]),
+    })
+}
+
+/// 32 bytes hash, with or without `0x` prefix.
+pub fn hash_bytes32(field: &'static str, value: &str) -> Result<B256, EncodingError> {
+    parse_hash(value)
+        .map(B256::from)
+        .map_err(|_| EncodingError::InvalidHex(field, value.to_string()))
+}
+
+fn scalar(field: &'static str, value: &str) -> Result<B256, EncodingError> {
+    let scalar = scalar_from_hex(value)
+        .map_err(|_| EncodingError::InvalidScalar(field, value.to_string()))?;
+    Ok(B256::from_slice(&scalar.to_bytes()))
+}
+
+fn version(value: &str) -> Result<u64, EncodingError> {
+    u64::from_str_radix(value.strip_prefix("0x").unwrap_or(value), 16)
+        .map_err(|_| EncodingError::InvalidNumber("version", value.to_string()))
+}
+
+fn clear_amount(value: &str) -> Result<U256, EncodingError> {
+    parse_amount(value)
+        .map(U256::from)
+        .map_err(|_| EncodingError::InvalidNumber("amount", value.to_string()))
+}
+
+fn domain(tag: &str) -> B256 {
+    B256::from(keccak256(tag.as_bytes()))
+}
+
+fn hash_encoding<T: SolValue>(encoding: &T) -> String {
+    hex::encode(keccak256(&encoding.abi_encode()))
+}
+
+impl LigthRangeProof {
+    pub fn encoding(&self) -> Result<RangeProofEncoding, EncodingError> {
+        Ok(RangeProofEncoding {
+            V: compressed_point("V", &self.V)?,
+            A: compressed_point("A", &self.A)?,
+            S: compressed_point("S", &self.S)?,
+            T1: compressed_point("T1", &self.T1)?,
+            T2: compressed_point("T2", &self.T2)?,
+            tx: scalar("tx", &self.tx)?,
+            txbf: scalar("txbf", &self.txbf)?,
+            e: scalar("e", &self.e)?,
+            a0: scalar("a0", &self.a0)?,
+            b0: scalar("b0", &self.b0)?,
+            L: self
+                .ind
+                .iter()
+                .map(|lr| compressed_point("L", &lr.L))
+                .collect::<Result<Vec<_>, _>>()?,
+            R: self
+                .ind
+                .iter()
+                .map(|lr| compressed_point("R", &lr.R))
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl PaymentUTXO {
+    pub fn encoding(&self) -> Result<PaymentUTXOEncoding, EncodingError> {
+        Ok(PaymentUTXOEncoding {
+            domain: domain(PAYMENT_UTXO_DOMAIN),
+            version: version(&self.version)?,
+            transactionHash: hash_bytes32("transaction_hash", &self.transaction_hash)?,
+            outputIndex: self.output_index,
+            publicKey: compressed_point("public_key", &self.public_key)?,
+            hasUnlockTime: self.unlock_time.is_some(),
+            unlockTime: self.unlock_time.unwrap_or(0),
+            amount: decode_hex(&self.amount)
+                .map_err(|_| EncodingError::InvalidHex("amount", self.amount.clone()))?,
+            currency: self.currency.clone(),
+            commitment: compressed_point("commitment", &self.commitment)?,
+            rangeProofHash: B256::from(keccak256(&self.rangeProof.encoding()?.abi_encode())),
+            rG: compressed_point("rG", &self.rG)?,
+        })
+    }
+
+    /// Hash of the content of the UTXO, whatever its `hash` field holds.
+    pub fn utxo_hash(&self) -> Result<String, EncodingError> {
+        Ok(hash_encoding(&self.encoding()?))
+    }
+}
+
+impl ExitUTXO {
+    pub fn encoding(&self) -> Result<ExitUTXOEncoding, EncodingError> {
+        Ok(ExitUTXOEncoding {
+            domain: domain(EXIT_UTXO_DOMAIN),
+            transactionHash: hash_bytes32("transaction_hash", &self.transaction_hash)?,
+            outputIndex: self.output_index,
+            publicKey: compressed_point("public_key", &self.public_key)?,
+            hasUnlockTime: self.unlock_time.is_some(),
+            unlockTime: self.unlock_time.unwrap_or(0),
+            amount: clear_amount(&self.amount)?,
+            currency: self.currency.clone(),
+            commitment: compressed_point("commitment", &self.commitment)?,
+            exitChain: self.exitChain.clone(),
+        })
+    }
+
+    /// Hash of the content of the UTXO, whatever its `hash` field holds.
+    pub fn utxo_hash(&self) -> Result<String, EncodingError> {
+        Ok(hash_encoding(&self.encoding()?))
+    }
+}
+
+impl CoinbaseUTXO {
+    pub fn encoding(&self) -> Result<CoinbaseUTXOEncoding, EncodingError> {
+        Ok(CoinbaseUTXOEncoding {
+            domain: domain(COINBASE_UTXO_DOMAIN),
+            version: version(&self.version)?,
+            transactionHash: hash_bytes32("transaction_hash", &self.transaction_hash)?,
+            outputIndex: self.output_index,
+            publicKey: compressed_point("public_key", &self.public_key)?,
+            hasUnlockTime: self.unlock_time.is_some(),
+            unlockTime: self.unlock_time.unwrap_or(0),
+            amount: clear_amount(&self.amount)?,
+            currency: self.currency.clone(),
+            commitment: compressed_point("commitment", &self.commitment)?,
+            rG: compressed_point("rG", &self.rG)?,
+        })
+    }
+
+    /// Hash of the content of the UTXO, whatever its `hash` field holds.
+    pub fn utxo_hash(&self) -> Result<String, EncodingError> {
+        Ok(hash_encoding(&self.encoding()?))
+    }
+}
+
+impl UTXO {
+    /// Canonical hash of the UTXO, hex encoded without prefix.
+    pub fn utxo_hash(&self) -> Result<String, EncodingError> {
+        match self {
+            UTXO::Payment(utxo) => utxo.utxo_hash(),
+            UTXO::Exit(utxo) => utxo.utxo_hash(),
+            UTXO::Coinbase(utxo) => utxo.utxo_hash(),
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod encoding;
+pub use encoding::*;
+mod variants;
+pub use variants::*;
+mod wire;
+pub use wire::*;
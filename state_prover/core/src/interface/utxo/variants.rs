@@ -0,0 +1,113 @@
+use crate::interface::range_proof::LigthRangeProof;
+use crate::interface::EncodingError;
+use alloc::boxed::Box;
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct PaymentUTXO {
+    pub version: String,          // hex version number of the transaction
+    pub transaction_hash: String, // hash of the transaction where this UTXO was output, coinbase transactions have a zero hash
+    pub output_index: u64,        // index number of the output in the transaction
+    pub public_key: String, // (compressed point) -> a one-time public key generated for this transaction output
+    pub unlock_time: Option<u64>,
+    pub amount: String, // encrypted amount + blinding factor, only the owner can decrypt it (if coinbase, the amount is clear and there is no blinding factor)
+    pub currency: String, // currency -> TODO: find a way to encrypt it too
+    pub commitment: String, // (compressed point) -> a cryptographic commitment to the amount, allows verification without revealing the amount
+    pub rangeProof: LigthRangeProof, // range proof of the amount
+    pub rG: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct ExitUTXO {
+    pub transaction_hash: String, // hash of the transaction where this UTXO was output, coinbase transactions have a zero hash
+    pub output_index: u64,        // index number of the output in the transaction
+    pub public_key: String, // (compressed point) -> a one-time public key generated for this transaction output
+    pub unlock_time: Option<u64>,
+    pub amount: String, // encrypted amount + blinding factor, only the owner can decrypt it (if coinbase, the amount is clear and there is no blinding factor)
+    pub currency: String, // currency -> TODO: find a way to encrypt it too
+    pub commitment: String, // (compressed point) -> a cryptographic commitment to the amount, allows verification without revealing the amount
+    pub exitChain: String,  // the chain where the UTXO is exiting
+    pub hash: String,       // hash of the UTXO
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct CoinbaseUTXO {
+    pub version: String,          // hex version number of the transaction
+    pub transaction_hash: String, // hash of the transaction where this UTXO was output, coinbase transactions have a zero hash
+    pub output_index: u64,        // index number of the output in the transaction
+    pub public_key: String, // (compressed point) -> a one-time public key generated for this transaction output
+    pub unlock_time: Option<u64>,
+    pub amount: String,     // coinbase amount is always clear
+    pub currency: String, // todo: mask this too using the same method as the amount (xor concat(8bytesAmount, currencyId) and shared secret) -> How to prove the currency input = currency output ???
+    pub commitment: String, // (compressed point) -> a cryptographic commitment to the amount, allows verification without revealing the amount
+    pub rG: String,
+    pub hash: String, // hash of the UTXO
+}
+
+impl PaymentUTXO {
+    /// Sets `hash` to the canonical hash of the content, see `utxo_hash`.
+    pub fn hashed(mut self) -> Result<Self, EncodingError> {
+        self.hash = self.utxo_hash()?;
+        Ok(self)
+    }
+}
+
+impl ExitUTXO {
+    /// Same as `PaymentUTXO::hashed`.
+    pub fn hashed(mut self) -> Result<Self, EncodingError> {
+        self.hash = self.utxo_hash()?;
+        Ok(self)
+    }
+}
+
+impl CoinbaseUTXO {
+    /// Same as `PaymentUTXO::hashed`.
+    pub fn hashed(mut self) -> Result<Self, EncodingError> {
+        self.hash = self.utxo_hash()?;
+        Ok(self)
+    }
+}
+
+// Define an enum to encapsulate the different UTXO types
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum UTXO {
+    Payment(Box<PaymentUTXO>),
+    Exit(ExitUTXO),
+    Coinbase(CoinbaseUTXO),
+}
+
+impl UTXO {
+    pub fn hash(&self) -> Option<&str> {
+        match self {
+            UTXO::Coinbase(utxo) => Some(&utxo.hash),
+            UTXO::Payment(utxo) => Some(&utxo.hash),
+            UTXO::Exit(utxo) => Some(&utxo.hash),
+        }
+    }
+
+    pub fn get_hash(&self) -> String {
+        match self {
+            UTXO::Coinbase(utxo) => utxo.hash.clone(),
+            UTXO::Payment(utxo) => utxo.hash.clone(),
+            UTXO::Exit(utxo) => utxo.hash.clone(),
+        }
+    }
+
+    pub fn get_public_key(&self) -> String {
+        match self {
+            UTXO::Coinbase(utxo) => utxo.public_key.clone(),
+            UTXO::Payment(utxo) => utxo.public_key.clone(),
+            UTXO::Exit(utxo) => utxo.public_key.clone(),
+        }
+    }
+
+    pub fn get_commitment(&self) -> String {
+        match self {
+            UTXO::Coinbase(utxo) => utxo.commitment.clone(),
+            UTXO::Payment(utxo) => utxo.commitment.clone(),
+            UTXO::Exit(utxo) => utxo.commitment.clone(),
+        }
+    }
+}
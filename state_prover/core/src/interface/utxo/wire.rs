@@ -0,0 +1,289 @@
+//! Binary wire format of the UTXOs.
+//!
+//! A UTXO is written as `[version][tag][payload]`: one byte for `UTXO_WIRE_VERSION`, one byte for
+//! the variant (`0` payment, `1` exit, `2` coinbase), then the fields of the variant in their
+//! declaration order. Integers are `u64` big endian, strings a `u32` big endian byte length
+//! followed by their UTF-8 bytes, options a `0` or `1` byte followed by the value when present,
+//! and the rounds of a range proof a `u32` count followed by each `L` and `R`. The layout is
+//! written by hand so that it does not move with serde or bincode, any change to it bumps the
+//! version.
+use crate::interface::range_proof::{LeftRigth, LigthRangeProof};
+use crate::interface::{CoinbaseUTXO, ExitUTXO, PaymentUTXO, UTXO};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+pub const UTXO_WIRE_VERSION: u8 = 1;
+
+const PAYMENT_TAG: u8 = 0;
+const EXIT_TAG: u8 = 1;
+const COINBASE_TAG: u8 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    UnexpectedEnd(usize), // offset of the truncated field
+    UnsupportedVersion(u8),
+    UnknownTag(u8),
+    InvalidOption(usize, u8), // offset, flag
+    InvalidUtf8(usize),       // offset of the string
+    TrailingBytes(usize),     // number of bytes left
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            WireError::UnexpectedEnd(offset) => {
+                write!(f, "Unexpected end of UTXO at byte {}", offset)
+            }
+            WireError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported UTXO wire version {}", version)
+            }
+            WireError::UnknownTag(tag) => write!(f, "Unknown UTXO variant {}", tag),
+            WireError::InvalidOption(offset, flag) => {
+                write!(f, "Invalid option flag {} at byte {}", flag, offset)
+            }
+            WireError::InvalidUtf8(offset) => write!(f, "Invalid UTF-8 string at byte {}", offset),
+            WireError::TrailingBytes(left) => write!(f, "{} trailing bytes after the UTXO", left),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WireError {}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u64(&mut self, value: u64) {
+        self.0.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        let len = u32::try_from(len).expect("wire lengths fit in 32 bits");
+        self.0.extend_from_slice(&len.to_be_bytes());
+    }
+
+    fn string(&mut self, value: &str) {
+        self.len(value.len());
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    fn option(&mut self, value: Option<u64>) {
+        match value {
+            Some(value) => {
+                self.0.push(1);
+                self.u64(value);
+            }
+            None => self.0.push(0),
+        }
+    }
+
+    fn range_proof(&mut self, proof: &LigthRangeProof) {
+        for field in [
+            &proof.V,
+            &proof.A,
+            &proof.S,
+            &proof.T1,
+            &proof.T2,
+            &proof.tx,
+            &proof.txbf,
+            &proof.e,
+            &proof.a0,
+            &proof.b0,
+        ] {
+            self.string(field);
+        }
+        self.len(proof.ind.len());
+        for round in &proof.ind {
+            self.string(&round.L);
+            self.string(&round.R);
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], WireError> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(WireError::UnexpectedEnd(self.offset))?;
+        let taken = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64, WireError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn len(&mut self) -> Result<usize, WireError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes(bytes.try_into().expect("4 bytes")) as usize)
+    }
+
+    fn string(&mut self) -> Result<String, WireError> {
+        let offset = self.offset;
+        let len = self.len()?;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| WireError::InvalidUtf8(offset))
+    }
+
+    fn option(&mut self) -> Result<Option<u64>, WireError> {
+        let offset = self.offset;
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.u64()?)),
+            flag => Err(WireError::InvalidOption(offset, flag)),
+        }
+    }
+
+    fn range_proof(&mut self) -> Result<LigthRangeProof, WireError> {
+        let mut proof = LigthRangeProof {
+            V: self.string()?,
+            A: self.string()?,
+            S: self.string()?,
+            T1: self.string()?,
+            T2: self.string()?,
+            tx: self.string()?,
+            txbf: self.string()?,
+            e: self.string()?,
+            a0: self.string()?,
+            b0: self.string()?,
+            ind: Vec::new(),
+        };
+        // no preallocation, the count is not trusted
+        for _ in 0..self.len()? {
+            proof.ind.push(LeftRigth {
+                L: self.string()?,
+                R: self.string()?,
+            });
+        }
+        Ok(proof)
+    }
+
+    fn payment(&mut self) -> Result<PaymentUTXO, WireError> {
+        Ok(PaymentUTXO {
+            version: self.string()?,
+            transaction_hash: self.string()?,
+            output_index: self.u64()?,
+            public_key: self.string()?,
+            unlock_time: self.option()?,
+            amount: self.string()?,
+            currency: self.string()?,
+            commitment: self.string()?,
+            rangeProof: self.range_proof()?,
+            rG: self.string()?,
+            hash: self.string()?,
+        })
+    }
+
+    fn exit(&mut self) -> Result<ExitUTXO, WireError> {
+        Ok(ExitUTXO {
+            transaction_hash: self.string()?,
+            output_index: self.u64()?,
+            public_key: self.string()?,
+            unlock_time: self.option()?,
+            amount: self.string()?,
+            currency: self.string()?,
+            commitment: self.string()?,
+            exitChain: self.string()?,
+            hash: self.string()?,
+        })
+    }
+
+    fn coinbase(&mut self) -> Result<CoinbaseUTXO, WireError> {
+        Ok(CoinbaseUTXO {
+            version: self.string()?,
+            transaction_hash: self.string()?,
+            output_index: self.u64()?,
+            public_key: self.string()?,
+            unlock_time: self.option()?,
+            amount: self.string()?,
+            currency: self.string()?,
+            commitment: self.string()?,
+            rG: self.string()?,
+            hash: self.string()?,
+        })
+    }
+}
+
+impl UTXO {
+    /// Wire encoding of the UTXO, in the current version.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer(Vec::new());
+        w.0.push(UTXO_WIRE_VERSION);
+        match self {
+            UTXO::Payment(utxo) => {
+                w.0.push(PAYMENT_TAG);
+                w.string(&utxo.version);
+                w.string(&utxo.transaction_hash);
+                w.u64(utxo.output_index);
+                w.string(&utxo.public_key);
+                w.option(utxo.unlock_time);
+                w.string(&utxo.amount);
+                w.string(&utxo.currency);
+                w.string(&utxo.commitment);
+                w.range_proof(&utxo.rangeProof);
+                w.string(&utxo.rG);
+                w.string(&utxo.hash);
+            }
+            UTXO::Exit(utxo) => {
+                w.0.push(EXIT_TAG);
+                w.string(&utxo.transaction_hash);
+                w.u64(utxo.output_index);
+                w.string(&utxo.public_key);
+                w.option(utxo.unlock_time);
+                w.string(&utxo.amount);
+                w.string(&utxo.currency);
+                w.string(&utxo.commitment);
+                w.string(&utxo.exitChain);
+                w.string(&utxo.hash);
+            }
+            UTXO::Coinbase(utxo) => {
+                w.0.push(COINBASE_TAG);
+                w.string(&utxo.version);
+                w.string(&utxo.transaction_hash);
+                w.u64(utxo.output_index);
+                w.string(&utxo.public_key);
+                w.option(utxo.unlock_time);
+                w.string(&utxo.amount);
+                w.string(&utxo.currency);
+                w.string(&utxo.commitment);
+                w.string(&utxo.rG);
+                w.string(&utxo.hash);
+            }
+        }
+        w.0
+    }
+
+    /// Decodes a UTXO written by `to_bytes`, the whole slice must be consumed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<UTXO, WireError> {
+        let mut r = Reader { bytes, offset: 0 };
+        let version = r.u8()?;
+        if version != UTXO_WIRE_VERSION {
+            return Err(WireError::UnsupportedVersion(version));
+        }
+        let utxo = match r.u8()? {
+            PAYMENT_TAG => UTXO::Payment(Box::new(r.payment()?)),
+            EXIT_TAG => UTXO::Exit(r.exit()?),
+            COINBASE_TAG => UTXO::Coinbase(r.coinbase()?),
+            tag => return Err(WireError::UnknownTag(tag)),
+        };
+        match bytes.len() - r.offset {
+            0 => Ok(utxo),
+            left => Err(WireError::TrailingBytes(left)),
+        }
+    }
+}
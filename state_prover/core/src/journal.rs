@@ -0,0 +1,26 @@
+//! Public output of the guest.
+//!
+//! The journal is the ABI encoding of `StateTransitionJournal`, written with
+//! `env::commit_slice`, so that the verifier contract reads it with
+//! `abi.decode(journal, (bytes32, bytes32, uint64, uint64, bytes32, bytes32, bytes32, bytes32))`.
+use alloy_primitives::B256;
+use alloy_sol_types::sol;
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct StateTransitionJournal {
+        bytes32 prevRoot;       // root of the state before the batch, see `StateCommitment::root`
+        bytes32 newRoot;        // root of the state after the batch
+        uint64 fromBlock;       // first block of the batch
+        uint64 toBlock;         // last block of the batch
+        bytes32 prevBatchHash;  // merkle root of the blocks before the batch, the last batch hash
+        bytes32 batchHash;      // merkle root of the blocks, up to the last block of the batch
+        bytes32 depositHash;    // hash of the deposits applied by the batch, zero without deposit
+        bytes32 withdrawalRoot; // merkle root of the exit outputs of the batch, zero without exit
+    }
+}
+
+/// Parses a hex encoded 32 bytes root, with or without `0x` prefix.
+pub fn root_to_bytes32(root: &str) -> Option<B256> {
+    root.parse().ok()
+}
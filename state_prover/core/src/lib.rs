@@ -0,0 +1,28 @@
+//! Types shared by the host, the guest and the publisher.
+//!
+//! Everything that ends up in the guest input or in the journal lives here, so that the host and
+//! the guest always agree on the encoding.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(non_snake_case)]
+
+extern crate alloc;
+
+pub mod crypto;
+pub use crypto::*;
+pub mod deposit;
+pub use deposit::*;
+pub mod hash;
+pub use hash::*;
+pub mod interface;
+pub use interface::*;
+pub mod journal;
+pub use journal::*;
+pub mod merkle;
+pub use merkle::*;
+#[cfg(feature = "std")]
+pub mod merkleTree;
+pub mod state;
+pub use state::*;
+pub mod transition;
+pub mod withdrawal;
+pub use withdrawal::*;
@@ -0,0 +1,132 @@
+//! Append-only Merkle accumulator, the incremental tree of the Ethereum deposit contract with
+//! Keccak-256 in place of SHA-256.
+//!
+//! Only the frontier is kept: the last left node of every height still waiting for its right
+//! sibling, which are the peaks of a Merkle mountain range. Appending a leaf hashes at most
+//! `ACCUMULATOR_DEPTH` nodes, and the root is computed from the frontier and the roots of the
+//! empty subtrees, whatever the number of leaves before.
+//! Pairs are hashed in position order, and the root mixes in the number of leaves:
+//! `keccak256(abi.encodePacked(node, uint256(count)))`.
+use crate::keccak256;
+use crate::merkle::{Hash, MerkleError};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+pub const ACCUMULATOR_DEPTH: usize = 32;
+
+fn hash_ordered(left: &Hash, right: &Hash) -> Hash {
+    let mut concatenated = [0u8; 64];
+    concatenated[..32].copy_from_slice(left);
+    concatenated[32..].copy_from_slice(right);
+    keccak256(&concatenated)
+}
+
+/// Frontier of the tree: the count of leaves and one peak per one bit of the count, the root of
+/// the complete subtree at that height, from the highest down.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleAccumulator {
+    count: u64,
+    peaks: Vec<Hash>,
+}
+
+impl MerkleAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Frontier of `count` leaves, as stored on chain.
+    pub fn from_frontier(count: u64, peaks: Vec<Hash>) -> Result<Self, MerkleError> {
+        let accumulator = MerkleAccumulator { count, peaks };
+        accumulator.check()?;
+        Ok(accumulator)
+    }
+
+    /// Number of leaves appended so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn peaks(&self) -> &[Hash] {
+        &self.peaks
+    }
+
+    pub fn append(&mut self, leaf: Hash) -> Result<(), MerkleError> {
+        self.check()?;
+        if self.count >= (1 << ACCUMULATOR_DEPTH) - 1 {
+            return Err(MerkleError::AccumulatorFull(self.count));
+        }
+        let mut size = self.count;
+        let mut node = leaf;
+        while size & 1 == 1 {
+            let peak = self.peaks.pop().expect("one peak per one bit");
+            node = hash_ordered(&peak, &node);
+            size >>= 1;
+        }
+        self.peaks.push(node);
+        self.count += 1;
+        Ok(())
+    }
+
+    pub fn root(&self) -> Result<Hash, MerkleError> {
+        self.check()?;
+        let mut peaks = self.peaks.iter().rev();
+        let mut node = [0u8; 32];
+        let mut zero = [0u8; 32]; // root of an empty subtree of the current height
+        let mut size = self.count;
+        for _ in 0..ACCUMULATOR_DEPTH {
+            node = match size & 1 == 1 {
+                true => hash_ordered(peaks.next().expect("one peak per one bit"), &node),
+                false => hash_ordered(&node, &zero),
+            };
+            zero = hash_ordered(&zero, &zero);
+            size >>= 1;
+        }
+        let mut count = [0u8; 32];
+        count[24..].copy_from_slice(&self.count.to_be_bytes());
+        Ok(hash_ordered(&node, &count))
+    }
+
+    /// Whether `leaf` is the last leaf appended, `proof` holding its left siblings inside the
+    /// lowest peak, from the bottom. The proof is empty when the count is odd.
+    pub fn is_last_leaf(&self, leaf: &Hash, proof: &[Hash]) -> bool {
+        if self.check().is_err()
+            || self.count == 0
+            || proof.len() != self.count.trailing_zeros() as usize
+        {
+            return false;
+        }
+        let node = proof
+            .iter()
+            .fold(*leaf, |node, sibling| hash_ordered(sibling, &node));
+        self.peaks.last() == Some(&node)
+    }
+
+    /// Proof for `is_last_leaf` built from every leaf appended so far.
+    pub fn last_leaf_proof(leaves: &[Hash]) -> Vec<Hash> {
+        if leaves.is_empty() {
+            return Vec::new();
+        }
+        // the lowest peak covers the last 2^k leaves, k the number of trailing zeros of the count
+        let size = 1 << leaves.len().trailing_zeros();
+        let mut level = leaves[leaves.len() - size..].to_vec();
+        let mut proof = Vec::new();
+        while level.len() > 1 {
+            proof.push(level[level.len() - 2]);
+            level = level
+                .chunks(2)
+                .map(|pair| hash_ordered(&pair[0], &pair[1]))
+                .collect();
+        }
+        proof
+    }
+
+    /// A deserialized frontier may not match its count.
+    fn check(&self) -> Result<(), MerkleError> {
+        if self.count >> ACCUMULATOR_DEPTH != 0
+            || self.peaks.len() != self.count.count_ones() as usize
+        {
+            return Err(MerkleError::InvalidFrontier(self.count, self.peaks.len()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Encodings of the data hashed into a leaf, chosen by the caller rather than guessed from a string.
+use crate::keccak256;
+use crate::merkle::{Hash, MerkleError};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::SolValue;
+use core::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Leaf {
+    /// `keccak256(bytes)`.
+    Raw(Vec<u8>),
+    /// `keccak256` of the decoded bytes, `0x` prefix optional and odd lengths rejected.
+    Hex(String),
+    /// `keccak256(abi.encodePacked(address, uint256))`.
+    PackedAddressAmount(Address, U256),
+    /// Leaf of the OpenZeppelin `StandardMerkleTree` over the ABI encoded values:
+    /// `keccak256(bytes.concat(keccak256(abi.encode(values))))`, with the values encoded as
+    /// `(address, amount).abi_encode_params()` for instance.
+    Standard(Vec<u8>),
+}
+
+impl Leaf {
+    /// `PackedAddressAmount` leaf of a `0x` prefixed address and a decimal amount.
+    pub fn address_amount(address: &str, amount: &str) -> Result<Self, MerkleError> {
+        let address = Address::from_str(address)
+            .map_err(|_| MerkleError::InvalidAddress(address.to_string()))?;
+        let amount = U256::from_str_radix(amount, 10)
+            .map_err(|_| MerkleError::InvalidAmount(amount.to_string()))?;
+        Ok(Leaf::PackedAddressAmount(address, amount))
+    }
+
+    pub fn hash(&self) -> Result<Hash, MerkleError> {
+        Ok(match self {
+            Leaf::Raw(bytes) => keccak256(bytes),
+            Leaf::Hex(data) => {
+                let bytes = hex::decode(data.strip_prefix("0x").unwrap_or(data))
+                    .map_err(|_| MerkleError::InvalidHex(data.clone()))?;
+                keccak256(&bytes)
+            }
+            Leaf::PackedAddressAmount(address, amount) => {
+                keccak256(&(*address, *amount).abi_encode_packed())
+            }
+            Leaf::Standard(encoded) => keccak256(&keccak256(encoded)),
+        })
+    }
+}
@@ -0,0 +1,12 @@
+pub mod accumulator;
+pub use accumulator::*;
+pub mod leaf;
+pub use leaf::*;
+pub mod multiproof;
+pub use multiproof::*;
+pub mod sparse;
+pub use sparse::*;
+pub mod standard;
+pub use standard::*;
+pub mod tree;
+pub use tree::*;
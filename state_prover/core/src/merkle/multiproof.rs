@@ -0,0 +1,160 @@
+//! Multi-leaf proofs in the format of the OpenZeppelin `MerkleProof.multiProofVerify`.
+//!
+//! The verifier consumes the leaves, then the hashes it computes, as a single queue: every flag
+//! hashes the next node of the queue with either the following one (`true`) or the next sibling
+//! of the proof (`false`). Each sibling shared by several leaves is hashed once.
+use crate::merkle::{hash_pair, Hash, MerkleError, MerkleTree};
+use alloc::collections::{BTreeSet, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct MultiProof {
+    pub indices: Vec<usize>, // index of each leaf in the tree, in the order of `leaves`
+    pub leaves: Vec<Hash>,
+    pub proof: Vec<Hash>,
+    pub proof_flags: Vec<bool>,
+}
+
+impl MultiProof {
+    pub fn verify(&self, root: &Hash) -> bool {
+        verify_multi_proof(root, &self.leaves, &self.proof, &self.proof_flags)
+    }
+}
+
+/// Root rebuilt from the leaves, the proof and the flags, as `processMultiProof` does.
+pub fn process_multi_proof(
+    leaves: &[Hash],
+    proof: &[Hash],
+    proof_flags: &[bool],
+) -> Result<Hash, MerkleError> {
+    if leaves.len() + proof.len() != proof_flags.len() + 1 {
+        return Err(MerkleError::InvalidMultiProof);
+    }
+    if proof_flags.is_empty() {
+        // a single leaf is its own root, and without any leaf the proof is the root
+        return Ok(match leaves.first() {
+            Some(leaf) => *leaf,
+            None => proof[0],
+        });
+    }
+    let mut hashes: Vec<Hash> = Vec::with_capacity(proof_flags.len());
+    let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0, 0, 0);
+    let mut next = |hashes: &Vec<Hash>| {
+        if leaf_pos < leaves.len() {
+            leaf_pos += 1;
+            Some(leaves[leaf_pos - 1])
+        } else {
+            hash_pos += 1;
+            hashes.get(hash_pos - 1).copied()
+        }
+    };
+    for &flag in proof_flags {
+        let a = next(&hashes).ok_or(MerkleError::InvalidMultiProof)?;
+        let b = match flag {
+            true => next(&hashes),
+            false => {
+                proof_pos += 1;
+                proof.get(proof_pos - 1).copied()
+            }
+        }
+        .ok_or(MerkleError::InvalidMultiProof)?;
+        hashes.push(hash_pair(&a, &b));
+    }
+    if proof_pos != proof.len() {
+        return Err(MerkleError::InvalidMultiProof);
+    }
+    Ok(hashes[hashes.len() - 1])
+}
+
+pub fn verify_multi_proof(
+    root: &Hash,
+    leaves: &[Hash],
+    proof: &[Hash],
+    proof_flags: &[bool],
+) -> bool {
+    process_multi_proof(leaves, proof, proof_flags).as_ref() == Ok(root)
+}
+
+impl MerkleTree {
+    /// Multiproof of the leaves at `indices`, duplicates ignored.
+    ///
+    /// The leaves are ordered from the deepest to the shallowest, right to left on a same depth,
+    /// which is the order the verifier queue needs. Promoted nodes make leaves sit at different
+    /// depths, and the queue can only mix two adjacent depths: leaves whose depths differ by
+    /// more than one are rejected with `UnsupportedLayout`. Leaves grouped by depth can always
+    /// be proven together.
+    pub fn generate_multi_proof(&self, indices: &[usize]) -> Result<MultiProof, MerkleError> {
+        let root = self.root().ok_or(MerkleError::EmptyTree)?;
+        let sizes: Vec<usize> = self.levels().iter().map(|level| level.len()).collect();
+        let top = (sizes.len() - 1, 0);
+        // a promoted node is identified with its highest copy, the one that gets hashed
+        let canonical = |(mut level, mut index): (usize, usize)| {
+            while level + 1 < sizes.len() && index == sizes[level] - 1 && sizes[level] % 2 == 1 {
+                level += 1;
+                index /= 2;
+            }
+            (level, index)
+        };
+        let parent = |(level, index): (usize, usize)| canonical((level + 1, index / 2));
+
+        let mut selected = BTreeSet::new();
+        for &index in indices {
+            if index >= sizes[0] {
+                return Err(MerkleError::LeafIndexOutOfBounds(index));
+            }
+            selected.insert(index);
+        }
+        if selected.is_empty() {
+            return Ok(MultiProof {
+                proof: vec![root],
+                ..Default::default()
+            });
+        }
+
+        // every node with a selected leaf below, along with the depth of each selected leaf
+        let mut known = BTreeSet::new();
+        let mut order = Vec::new();
+        for &index in &selected {
+            let mut node = canonical((0, index));
+            let mut depth = 0;
+            known.insert(node);
+            while node != top {
+                node = parent(node);
+                known.insert(node);
+                depth += 1;
+            }
+            order.push((Reverse(depth), Reverse(index)));
+        }
+        order.sort();
+        let indices: Vec<usize> = order.iter().map(|(_, Reverse(index))| *index).collect();
+
+        let mut queue: VecDeque<(usize, usize)> =
+            indices.iter().map(|&index| canonical((0, index))).collect();
+        let mut proof = Vec::new();
+        let mut proof_flags = Vec::new();
+        while queue.len() > 1 || queue[0] != top {
+            let node = queue.pop_front().expect("queue is not empty");
+            let sibling = (node.0, node.1 ^ 1);
+            if queue.front() == Some(&sibling) {
+                queue.pop_front();
+                proof_flags.push(true);
+            } else if known.contains(&sibling) {
+                return Err(MerkleError::UnsupportedLayout);
+            } else {
+                proof.push(self.levels()[sibling.0][sibling.1]);
+                proof_flags.push(false);
+            }
+            queue.push_back(parent(node));
+        }
+
+        Ok(MultiProof {
+            leaves: indices.iter().map(|&index| self.leaves()[index]).collect(),
+            indices,
+            proof,
+            proof_flags,
+        })
+    }
+}
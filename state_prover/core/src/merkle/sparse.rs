@@ -0,0 +1,253 @@
+//! Sparse Merkle tree of depth 256 keyed by 32 bytes hashes.
+//!
+//! Every possible key has a leaf, so absence is proven like presence: the leaf of a missing key is
+//! the empty node. Empty subtrees hash to the empty node (`H(0, 0) = 0`), so only the path of the
+//! keys actually set is stored, and proofs only carry the non-empty siblings along with a bitmap
+//! of their heights.
+//!
+//! Heights count from the leaves (0) to the root (256). The bit of the key at height `h`, counted
+//! from the least significant bit, tells whether the node at height `h` is a right child.
+use crate::keccak256;
+use crate::merkle::{Hash, MerkleError};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+pub const SPARSE_DEPTH: usize = 256;
+
+/// Hash of every empty subtree, and root of the empty tree.
+pub const EMPTY_NODE: Hash = [0u8; 32];
+
+/// Leaf of a key set to `value`: `keccak256(key || value)`.
+pub fn sparse_leaf(key: &Hash, value: &Hash) -> Hash {
+    let mut concatenated = [0u8; 64];
+    concatenated[..32].copy_from_slice(key);
+    concatenated[32..].copy_from_slice(value);
+    keccak256(&concatenated)
+}
+
+/// Inner node: `keccak256(left || right)`, or the empty node when both children are empty.
+pub fn sparse_node(left: &Hash, right: &Hash) -> Hash {
+    if *left == EMPTY_NODE && *right == EMPTY_NODE {
+        return EMPTY_NODE;
+    }
+    let mut concatenated = [0u8; 64];
+    concatenated[..32].copy_from_slice(left);
+    concatenated[32..].copy_from_slice(right);
+    keccak256(&concatenated)
+}
+
+fn bit(bits: &Hash, height: usize) -> bool {
+    bits[31 - height / 8] >> (height % 8) & 1 == 1
+}
+
+/// Key of the ancestor at `height`: the key with its `height` lowest bits cleared.
+fn prefix(key: &Hash, height: usize) -> Hash {
+    let mut prefix = *key;
+    let full = height / 8;
+    prefix[32 - full..].fill(0);
+    if full < 32 {
+        prefix[31 - full] &= 0xff << (height % 8);
+    }
+    prefix
+}
+
+fn sibling_prefix(key: &Hash, height: usize) -> Hash {
+    let mut sibling = prefix(key, height);
+    sibling[31 - height / 8] ^= 1 << (height % 8);
+    sibling
+}
+
+/// Compressed proof: the non-empty siblings from the bottom up, and the heights where they sit
+/// as a bitmap laid out like the keys.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseProof {
+    pub bitmap: Hash,
+    pub siblings: Vec<Hash>,
+}
+
+impl SparseProof {
+    /// Root of a tree where the leaf of `key` is `leaf` and the other nodes are those of the proof.
+    pub fn compute_root(&self, key: &Hash, leaf: &Hash) -> Result<Hash, MerkleError> {
+        let expected = self.bitmap.iter().map(|b| b.count_ones() as usize).sum();
+        if self.siblings.len() != expected {
+            return Err(MerkleError::MalformedProof(expected, self.siblings.len()));
+        }
+        let mut siblings = self.siblings.iter();
+        let mut node = *leaf;
+        for height in 0..SPARSE_DEPTH {
+            let sibling = match bit(&self.bitmap, height) {
+                true => *siblings.next().expect("counted above"),
+                false => EMPTY_NODE,
+            };
+            node = match bit(key, height) {
+                true => sparse_node(&sibling, &node),
+                false => sparse_node(&node, &sibling),
+            };
+        }
+        Ok(node)
+    }
+}
+
+/// Checks that `key` is set to `value` in the tree of `root`.
+pub fn verify_inclusion(root: &Hash, key: &Hash, value: &Hash, proof: &SparseProof) -> bool {
+    proof.compute_root(key, &sparse_leaf(key, value)).as_ref() == Ok(root)
+}
+
+/// Checks that `key` is not set in the tree of `root`.
+pub fn verify_exclusion(root: &Hash, key: &Hash, proof: &SparseProof) -> bool {
+    proof.compute_root(key, &EMPTY_NODE).as_ref() == Ok(root)
+}
+
+/// Update of a single key, with the proof of its path before the update.
+///
+/// A sequence of witnesses taken one after the other moves a root from one state to the next, so
+/// the guest can follow the updates of a batch without holding the whole set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SparseWitness {
+    pub key: Hash,
+    pub old_value: Option<Hash>, // None when the key was not set
+    pub new_value: Option<Hash>, // None when the key is deleted
+    pub proof: SparseProof,
+}
+
+impl SparseWitness {
+    /// Checks the old value of the key against `root` and returns the root after the update.
+    pub fn apply(&self, root: &Hash) -> Result<Hash, MerkleError> {
+        let computed = self
+            .proof
+            .compute_root(&self.key, &self.leaf(&self.old_value))?;
+        if computed != *root {
+            return Err(MerkleError::RootMismatch(*root, computed));
+        }
+        self.proof
+            .compute_root(&self.key, &self.leaf(&self.new_value))
+    }
+
+    fn leaf(&self, value: &Option<Hash>) -> Hash {
+        match value {
+            Some(value) => sparse_leaf(&self.key, value),
+            None => EMPTY_NODE,
+        }
+    }
+}
+
+/// Applies a sequence of witnesses to `root`.
+pub fn apply_witnesses(root: &Hash, witnesses: &[SparseWitness]) -> Result<Hash, MerkleError> {
+    witnesses
+        .iter()
+        .try_fold(*root, |root, witness| witness.apply(&root))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SparseMerkleTree {
+    values: BTreeMap<Hash, Hash>,
+    nodes: BTreeMap<(u16, Hash), Hash>, // non-empty nodes by height and prefix
+}
+
+impl SparseMerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn root(&self) -> Hash {
+        self.node(SPARSE_DEPTH, &EMPTY_NODE)
+    }
+
+    pub fn get(&self, key: &Hash) -> Option<&Hash> {
+        self.values.get(key)
+    }
+
+    pub fn contains(&self, key: &Hash) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Keys and values in ascending key order.
+    pub fn iter(&self) -> impl Iterator<Item = (&Hash, &Hash)> {
+        self.values.iter()
+    }
+
+    /// Sets `key` to `value`, returns the previous value.
+    pub fn insert(&mut self, key: Hash, value: Hash) -> Option<Hash> {
+        self.update(key, Some(value)).old_value
+    }
+
+    /// Deletes `key`, returns its value.
+    pub fn remove(&mut self, key: &Hash) -> Option<Hash> {
+        self.update(*key, None).old_value
+    }
+
+    /// Sets or deletes `key` and returns the witness of the update.
+    pub fn update(&mut self, key: Hash, value: Option<Hash>) -> SparseWitness {
+        let proof = self.prove(&key);
+        let old_value = match value {
+            Some(value) => self.values.insert(key, value),
+            None => self.values.remove(&key),
+        };
+        let mut node = match value {
+            Some(value) => sparse_leaf(&key, &value),
+            None => EMPTY_NODE,
+        };
+        for height in 0..SPARSE_DEPTH {
+            self.set_node(height, prefix(&key, height), node);
+            let sibling = self.node(height, &sibling_prefix(&key, height));
+            node = match bit(&key, height) {
+                true => sparse_node(&sibling, &node),
+                false => sparse_node(&node, &sibling),
+            };
+        }
+        self.set_node(SPARSE_DEPTH, EMPTY_NODE, node);
+        SparseWitness {
+            key,
+            old_value,
+            new_value: value,
+            proof,
+        }
+    }
+
+    /// Proof of the path of `key`, an inclusion proof if it is set and an exclusion one otherwise.
+    pub fn prove(&self, key: &Hash) -> SparseProof {
+        let mut proof = SparseProof::default();
+        for height in 0..SPARSE_DEPTH {
+            let sibling = self.node(height, &sibling_prefix(key, height));
+            if sibling != EMPTY_NODE {
+                proof.bitmap[31 - height / 8] |= 1 << (height % 8);
+                proof.siblings.push(sibling);
+            }
+        }
+        proof
+    }
+
+    fn node(&self, height: usize, prefix: &Hash) -> Hash {
+        self.nodes
+            .get(&(height as u16, *prefix))
+            .copied()
+            .unwrap_or(EMPTY_NODE)
+    }
+
+    fn set_node(&mut self, height: usize, prefix: Hash, node: Hash) {
+        if node == EMPTY_NODE {
+            self.nodes.remove(&(height as u16, prefix));
+        } else {
+            self.nodes.insert((height as u16, prefix), node);
+        }
+    }
+}
+
+impl FromIterator<(Hash, Hash)> for SparseMerkleTree {
+    fn from_iter<I: IntoIterator<Item = (Hash, Hash)>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        for (key, value) in iter {
+            tree.insert(key, value);
+        }
+        tree
+    }
+}
@@ -0,0 +1,71 @@
+//! Tree of the OpenZeppelin `StandardMerkleTree`, as built by `StandardMerkleTree.of`.
+//!
+//! The leaves are sorted, then stored in reverse order at the end of an array holding a complete
+//! binary tree: node `i` has the children `2i + 1` and `2i + 2`, and the root is node 0. Pairs
+//! are sorted before being hashed, so the proofs are checked with `verify_proof`, as
+//! `MerkleProof.verify` does on chain. Unlike `MerkleTree`, no node is promoted, a tree of any
+//! size has the root the JavaScript library computes for the same leaves.
+use crate::merkle::{hash_pair, Hash};
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StandardMerkleTree {
+    nodes: Vec<Hash>,
+    leaves: Vec<Hash>, // sorted
+}
+
+impl StandardMerkleTree {
+    /// Builds the tree over already hashed leaves, in any order.
+    pub fn new(mut leaves: Vec<Hash>) -> Self {
+        leaves.sort();
+        if leaves.is_empty() {
+            return StandardMerkleTree {
+                nodes: Vec::new(),
+                leaves,
+            };
+        }
+        let mut nodes = vec![[0u8; 32]; 2 * leaves.len() - 1];
+        let last = nodes.len() - 1;
+        for (i, leaf) in leaves.iter().enumerate() {
+            nodes[last - i] = *leaf;
+        }
+        for i in (0..nodes.len() - leaves.len()).rev() {
+            nodes[i] = hash_pair(&nodes[2 * i + 1], &nodes[2 * i + 2]);
+        }
+        StandardMerkleTree { nodes, leaves }
+    }
+
+    /// Root of the tree, `None` when it has no leaf.
+    pub fn root(&self) -> Option<Hash> {
+        self.nodes.first().copied()
+    }
+
+    /// Leaves in the order of the tree, sorted.
+    pub fn leaves(&self) -> &[Hash] {
+        &self.leaves
+    }
+
+    /// Index of the first leaf equal to `leaf`, in the sorted leaves.
+    pub fn locate_leaf(&self, leaf: &Hash) -> Option<usize> {
+        let index = self.leaves.partition_point(|other| other < leaf);
+        (self.leaves.get(index) == Some(leaf)).then_some(index)
+    }
+
+    /// Siblings of a leaf from the bottom up, as `getProof` returns them.
+    ///
+    /// Returns `None` when the index is out of bounds.
+    pub fn generate_proof(&self, leaf_index: usize) -> Option<Vec<Hash>> {
+        if leaf_index >= self.leaves.len() {
+            return None;
+        }
+        let mut proof = Vec::new();
+        let mut index = self.nodes.len() - 1 - leaf_index;
+        while index > 0 {
+            let sibling = if index % 2 == 1 { index + 1 } else { index - 1 };
+            proof.push(self.nodes[sibling]);
+            index = (index - 1) / 2;
+        }
+        Some(proof)
+    }
+}
@@ -0,0 +1,203 @@
+//! Keccak Merkle tree over 32 bytes nodes.
+//!
+//! Same semantics as the OpenZeppelin `MerkleProof` library and the legacy hex string tree:
+//! leaves are hashed with Keccak-256, pairs are sorted before being hashed, and the last node of
+//! an odd-sized level is promoted to the next level unhashed. Levels are stored bottom-up, the
+//! last one holding the root.
+use crate::keccak256;
+use crate::merkle::Leaf;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+pub type Hash = [u8; 32];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleError {
+    InvalidHex(String),
+    InvalidAddress(String),
+    InvalidAmount(String),
+    InvalidHash(String),
+    MalformedProof(usize, usize), // expected siblings, given siblings
+    RootMismatch(Hash, Hash),     // expected, computed
+    AccumulatorFull(u64),         // number of leaves
+    InvalidFrontier(u64, usize),  // number of leaves, number of peaks
+    EmptyTree,
+    LeafIndexOutOfBounds(usize),
+    InvalidMultiProof,
+    UnsupportedLayout,
+}
+
+impl Display for MerkleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            MerkleError::InvalidHex(s) => write!(f, "Invalid hex leaf {}", s),
+            MerkleError::InvalidAddress(s) => write!(f, "Invalid address {}", s),
+            MerkleError::InvalidAmount(s) => write!(f, "Invalid amount {}", s),
+            MerkleError::InvalidHash(s) => write!(f, "Invalid 32 bytes hash {}", s),
+            MerkleError::MalformedProof(expected, given) => write!(
+                f,
+                "Malformed proof, expected {} siblings, got {}",
+                expected, given
+            ),
+            MerkleError::RootMismatch(expected, computed) => write!(
+                f,
+                "Root mismatch, expected {}, computed {}",
+                hex::encode(expected),
+                hex::encode(computed)
+            ),
+            MerkleError::AccumulatorFull(count) => {
+                write!(f, "Accumulator is full with {} leaves", count)
+            }
+            MerkleError::InvalidFrontier(count, peaks) => {
+                write!(f, "Invalid frontier, {} peaks for {} leaves", peaks, count)
+            }
+            MerkleError::EmptyTree => write!(f, "Tree has no leaf"),
+            MerkleError::LeafIndexOutOfBounds(index) => {
+                write!(f, "Leaf index {} is out of bounds", index)
+            }
+            MerkleError::InvalidMultiProof => write!(f, "Invalid multiproof"),
+            MerkleError::UnsupportedLayout => write!(
+                f,
+                "Leaves are too far apart in depth to be proven by a single multiproof"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MerkleError {}
+
+/// Keccak-256 of the sorted pair.
+pub fn hash_pair(a: &Hash, b: &Hash) -> Hash {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut concatenated = [0u8; 64];
+    concatenated[..32].copy_from_slice(first);
+    concatenated[32..].copy_from_slice(second);
+    keccak256(&concatenated)
+}
+
+/// Leaf hash of hex encoded data, see `Leaf::Hex`.
+pub fn hex_leaf(data: &str) -> Result<Hash, MerkleError> {
+    Leaf::Hex(data.to_string()).hash()
+}
+
+/// Decodes a hex encoded 32 bytes hash, `0x` prefix optional.
+pub fn parse_hash(hash: &str) -> Result<Hash, MerkleError> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hash.strip_prefix("0x").unwrap_or(hash), &mut bytes)
+        .map_err(|_| MerkleError::InvalidHash(hash.to_string()))?;
+    Ok(bytes)
+}
+
+/// Root obtained by folding the siblings of `proof` into `leaf`.
+pub fn process_proof(leaf: &Hash, proof: &[Hash]) -> Hash {
+    proof
+        .iter()
+        .fold(*leaf, |node, sibling| hash_pair(&node, sibling))
+}
+
+/// Checks a proof produced by `MerkleTree::generate_proof` against a root.
+///
+/// Pairs are sorted, so the position of the leaf is not needed, and promoted levels contribute no
+/// sibling, so the proof of a leaf only holds the hashes actually combined with its path.
+pub fn verify_proof(root: &Hash, leaf: &Hash, proof: &[Hash]) -> bool {
+    process_proof(leaf, proof) == *root
+}
+
+/// Same as `verify_proof` for hex encoded hashes, such as the `0x` prefixed proofs of the legacy
+/// hex string tree.
+pub fn verify_hex_proof<T: AsRef<str>>(
+    root: &str,
+    leaf: &str,
+    proof: &[T],
+) -> Result<bool, MerkleError> {
+    let proof = proof
+        .iter()
+        .map(|sibling| parse_hash(sibling.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(verify_proof(&parse_hash(root)?, &parse_hash(leaf)?, &proof))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree over already hashed leaves.
+    pub fn new(leaves: Vec<Hash>) -> Self {
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let level = &levels[levels.len() - 1];
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        MerkleTree { levels }
+    }
+
+    /// Builds the tree over raw data, hashing every item into a leaf.
+    pub fn from_data<T: AsRef<[u8]>>(data: &[T]) -> Self {
+        Self::new(data.iter().map(|item| keccak256(item.as_ref())).collect())
+    }
+
+    /// Builds the tree over leaves of any encoding.
+    pub fn from_leaves(leaves: &[Leaf]) -> Result<Self, MerkleError> {
+        let leaves = leaves.iter().map(Leaf::hash).collect::<Result<_, _>>()?;
+        Ok(Self::new(leaves))
+    }
+
+    /// Builds the tree over hex encoded data.
+    pub fn from_hex<T: AsRef<str>>(data: &[T]) -> Result<Self, MerkleError> {
+        let leaves = data
+            .iter()
+            .map(|item| hex_leaf(item.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(leaves))
+    }
+
+    /// Root of the tree, `None` when it has no leaf.
+    pub fn root(&self) -> Option<Hash> {
+        self.levels[self.levels.len() - 1].first().copied()
+    }
+
+    pub fn leaves(&self) -> &[Hash] {
+        &self.levels[0]
+    }
+
+    /// Levels from the leaves up to the root.
+    pub fn levels(&self) -> &[Vec<Hash>] {
+        &self.levels
+    }
+
+    /// Index of the first leaf equal to `leaf`.
+    pub fn locate_leaf(&self, leaf: &Hash) -> Option<usize> {
+        self.levels[0].iter().position(|node| node == leaf)
+    }
+
+    /// Siblings of a leaf from the bottom up, promoted nodes have no sibling on their level.
+    ///
+    /// Returns `None` when the index is out of bounds.
+    pub fn generate_proof(&self, leaf_index: usize) -> Option<Vec<Hash>> {
+        if leaf_index >= self.levels[0].len() {
+            return None;
+        }
+        let mut proof = Vec::new();
+        let mut index = leaf_index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
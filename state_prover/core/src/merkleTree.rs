@@ -1,44 +1,14 @@
+//! Legacy Merkle tree over hex `String` nodes, kept as the reference of the merkle benchmark.
+//! New code should use the 32 bytes tree of `crate::merkle`.
 extern crate petgraph;
 
+use crate::merkle::Leaf;
 use hex;
 use std::fmt::Display;
-use tiny_keccak::{Hasher, Keccak};
 #[derive(Debug)]
 pub enum BytesError {
     ComparisonFailed(String, String),
     ConcatenateError(String, String),
-    KeccakError(String),
-}
-
-fn keccak256(input: &str) -> Result<String, BytesError> {
-    let mut value;
-    if input.contains(",") {
-        let inputs: Vec<&str> = input.split(",").collect();
-        value = encode_packed(inputs[0].trim(), inputs[1].trim());
-    } else {
-        value = if input.starts_with("0x") {
-            input[2..].to_string()
-        } else {
-            input.to_string()
-        };
-
-        if value.len() % 2 != 0 {
-            value.insert(0, '0');
-        }
-    }
-    let hash = match hex::decode(&value) {
-        Ok(t) => t,
-        Err(e) => {
-            println!("{}", e);
-            return Err(BytesError::KeccakError(value));
-        }
-    };
-    let mut digest = [0u8; 32];
-    let mut hasher = Keccak::v256();
-    hasher.update(&*hash);
-    hasher.finalize(&mut digest);
-    let hex_string: Vec<String> = digest.iter().map(|b| format!("{:02x}", b)).collect();
-    Ok(hex_string.concat())
 }
 
 impl Display for BytesError {
@@ -50,7 +20,6 @@ impl Display for BytesError {
             BytesError::ConcatenateError(a, b) => {
                 write!(f, "Concatenate error between {} and {}", a, b)
             }
-            BytesError::KeccakError(s) => write!(f, "Keccak error for {}", s),
         }
     }
 }
@@ -60,21 +29,6 @@ impl std::error::Error for BytesError {}
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 
-pub fn encode_packed(addr_str: &str, amount_str: &str) -> String {
-    let addr_bytes = hex::decode(&addr_str[2..]).expect("Failed to decode address");
-    let mut addr_padded = vec![0u8; 32];
-    addr_padded[12..].copy_from_slice(&addr_bytes);
-    let amount: u64 = amount_str.parse().expect("Failed to parse amount");
-    let mut amount_bytes = [0u8; 32];
-    for (i, byte) in amount.to_be_bytes().iter().rev().enumerate() {
-        amount_bytes[31 - i] = *byte;
-    }
-    let mut packed = Vec::new();
-    packed.extend_from_slice(&addr_padded);
-    packed.extend_from_slice(&amount_bytes);
-    packed.iter().map(|byte| format!("{:02x}", byte)).collect()
-}
-
 pub fn compare_bytes(a: &str, b: &str) -> Result<std::cmp::Ordering, hex::FromHexError> {
     let a_bytes = hex::decode(a)?;
     let b_bytes = hex::decode(b)?;
@@ -106,12 +60,10 @@ pub fn hash_pair(a: &str, b: &str) -> Result<String, BytesError> {
         }
     };
     let concatenated = match concat_hex_strings(sorted.0, sorted.1) {
-        Ok(t) => {
-            format!("0x{}", t)
-        }
+        Ok(t) => hex::decode(t).expect("Concatenation is hex encoded"),
         Err(_) => return Err(BytesError::ConcatenateError(a.to_string(), b.to_string())),
     };
-    keccak256(concatenated.as_str())
+    Ok(hex::encode(crate::keccak256(&concatenated)))
 }
 /// Represents a node in the Merkle Tree.
 pub struct MerkleNode {
@@ -125,18 +77,18 @@ pub struct MerkleTree {
 }
 
 impl MerkleTree {
-    /// Creates a new MerkleTree based on the provided data.
+    /// Creates a new MerkleTree over the provided leaves, each hashed with its own encoding.
     ///
     /// # Errors
     ///
-    /// - When there's a problem hashing the data with `keccak256`.
+    /// - When a leaf does not match its encoding.
     /// - When the `hash_pair` function encounters issues.
-    pub fn new(data: &Vec<String>) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(data: &[Leaf]) -> Result<Self, Box<dyn std::error::Error>> {
         let mut graph = DiGraph::new();
         let mut nodes: Vec<String> = data
-            .into_iter()
-            .map(|node| keccak256(node.as_str()).expect("Keccak Error."))
-            .collect();
+            .iter()
+            .map(|leaf| leaf.hash().map(hex::encode))
+            .collect::<Result<_, _>>()?;
         let mut previous_layer_indices = Vec::new();
 
         for node_data in &nodes {
@@ -0,0 +1,123 @@
+use crate::crypto::{point_from_hex, point_to_bytes, CurveError};
+use crate::interface::UTXO;
+use crate::keccak256;
+use crate::merkle::{parse_hash, Hash, MerkleError, SparseMerkleTree, SparseWitness, EMPTY_NODE};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// Value of the keys of the state trees: both are sets, a key is set when its member is.
+pub const MEMBER_VALUE: Hash = [0u8; 32];
+
+/// Key of a UTXO in the UTXO tree: its hash.
+pub fn utxo_key(hash: &str) -> Result<Hash, MerkleError> {
+    parse_hash(hash)
+}
+
+/// Key of a deposit in the deposit tree: the hash of its transaction on the root chain.
+pub fn deposit_key(tx_id: &str) -> Result<Hash, MerkleError> {
+    parse_hash(tx_id)
+}
+
+/// Key of a key image in the key image tree: `keccak256` of its compressed encoding, so that two
+/// encodings of the same key image share the key.
+pub fn key_image_key(key_image: &str) -> Result<Hash, CurveError> {
+    point_from_hex(key_image).map(|point| keccak256(&point_to_bytes(&point)))
+}
+
+/// Full state at a given height, held by the host: the UTXO set, the spent key images and the
+/// applied deposits, each in a sparse tree whose keys are `utxo_key`, `key_image_key` and
+/// `deposit_key`. The guest only gets the roots and the witnesses of the updates of every block,
+/// see `transition::execute`.
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    pub(crate) utxos: BTreeMap<Hash, UTXO>,
+    pub(crate) utxo_tree: SparseMerkleTree,
+    pub(crate) key_image_tree: SparseMerkleTree,
+    pub(crate) deposit_tree: SparseMerkleTree,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a UTXO outside of any transaction, to build a genesis state. Returns `false` if it
+    /// was already part of the set.
+    pub fn insert_utxo(&mut self, utxo: UTXO) -> Result<bool, MerkleError> {
+        let key = utxo_key(&utxo.get_hash())?;
+        self.utxo_tree.insert(key, MEMBER_VALUE);
+        Ok(self.utxos.insert(key, utxo).is_none())
+    }
+
+    pub fn utxo(&self, hash: &str) -> Option<&UTXO> {
+        utxo_key(hash).ok().and_then(|key| self.utxos.get(&key))
+    }
+
+    /// UTXOs in ascending hash order.
+    pub fn utxos(&self) -> impl Iterator<Item = &UTXO> {
+        self.utxos.values()
+    }
+
+    pub fn is_spent(&self, key_image: &str) -> bool {
+        key_image_key(key_image).is_ok_and(|key| self.key_image_tree.contains(&key))
+    }
+
+    /// Whether the deposit of the root chain transaction `tx_id` has been applied.
+    pub fn is_deposited(&self, tx_id: &str) -> bool {
+        deposit_key(tx_id).is_ok_and(|key| self.deposit_tree.contains(&key))
+    }
+
+    pub fn commitment(&self) -> StateCommitment {
+        StateCommitment {
+            utxo_root: hex::encode(self.utxo_tree.root()),
+            key_image_root: hex::encode(self.key_image_tree.root()),
+            deposit_root: hex::encode(self.deposit_tree.root()),
+        }
+    }
+}
+
+/// Updates of the state trees by the transactions of a block, in the order the guest checks them.
+///
+/// For every transaction, `utxos` proves each ring member part of the set, then inserts each
+/// output, or proves it absent for an exit output, which leaves the set. `key_images` proves each
+/// key image absent from the spent ones before inserting it, and `deposits` each deposit absent
+/// from the applied ones. `members` holds the content of the ring members, once each.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockWitness {
+    pub members: Vec<UTXO>,
+    pub utxos: Vec<SparseWitness>,
+    pub key_images: Vec<SparseWitness>,
+    pub deposits: Vec<SparseWitness>,
+}
+
+/// Roots committing to a `State`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct StateCommitment {
+    pub utxo_root: String,
+    pub key_image_root: String,
+    pub deposit_root: String,
+}
+
+impl Default for StateCommitment {
+    /// Commitment of the empty state.
+    fn default() -> Self {
+        StateCommitment {
+            utxo_root: hex::encode(EMPTY_NODE),
+            key_image_root: hex::encode(EMPTY_NODE),
+            deposit_root: hex::encode(EMPTY_NODE),
+        }
+    }
+}
+
+impl StateCommitment {
+    /// Single root of the state: `keccak256(utxo_root || key_image_root || deposit_root)`.
+    pub fn root(&self) -> Result<Hash, MerkleError> {
+        let mut bytes = [0u8; 96];
+        bytes[..32].copy_from_slice(&parse_hash(&self.utxo_root)?);
+        bytes[32..64].copy_from_slice(&parse_hash(&self.key_image_root)?);
+        bytes[64..].copy_from_slice(&parse_hash(&self.deposit_root)?);
+        Ok(keccak256(&bytes))
+    }
+}
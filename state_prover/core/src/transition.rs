@@ -0,0 +1,681 @@
+use crate::crypto::{
+    decode_signature, verify_batch, verify_clear_commitment, verify_ring_signature, BalanceError,
+    CommitmentError, Generators, MlsagError, RangeProofError,
+};
+use crate::deposit::{deposit_hash, Deposit};
+use crate::interface::{
+    Block, BlockError, CoinbaseUTXO, DepositError, EncodingError, ExitUTXO, Inputs,
+    PendingTransaction, PendingUserDepositTx, UTXO,
+};
+use crate::journal::{root_to_bytes32, StateTransitionJournal};
+use crate::merkle::{parse_hash, Hash, MerkleAccumulator, MerkleError, SparseWitness};
+use crate::state::{
+    deposit_key, key_image_key, utxo_key, BlockWitness, State, StateCommitment, MEMBER_VALUE,
+};
+use crate::withdrawal::{withdrawal_root, Withdrawal, WithdrawalError};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloy_primitives::B256;
+use core::fmt::Display;
+
+/// Root of an empty UTXO or key image set.
+pub const EMPTY_ROOT: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug)]
+pub enum TransitionError {
+    UnknownInput(String, String),
+    DuplicateOutput(String, String),
+    InvalidSignature(String, MlsagError),
+    DoubleSpend(String, String),
+    Unbalanced(String, BalanceError),
+    InvalidRangeProof(String, String, RangeProofError),
+    InvalidCommitment(String, String, CommitmentError),
+    RootMismatch(String, String), // expected, computed
+    EmptyBatch,
+    InvalidRoot(String),
+    MerkleError(String),
+    InvalidTransactionHash(String, String), // claimed, computed
+    InvalidUTXOHash(String, String),        // claimed, computed
+    InvalidBlock(u64, BlockError),          // height
+    UnexpectedHeight(u64, u64),             // expected, header
+    BrokenChain(u64, String),               // height, parent hash
+    UTXORootMismatch(u64, String, String),  // height, header, computed
+    InvalidExit(String, String, WithdrawalError),
+    InvalidEncoding(String, EncodingError), // claimed hash
+    WitnessCount(usize, usize),             // blocks, witnesses
+    MissingWitness(String),                 // key
+    UnexpectedWitness(String),              // key of the witness
+    InvalidWitness(String, MerkleError),    // key
+    InvalidDeposit(String, DepositError),
+    DuplicateDeposit(String, String), // transaction, root chain transaction id
+}
+
+impl Display for TransitionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            TransitionError::UnknownInput(tx, input) => {
+                write!(f, "Transaction {} spends unknown UTXO {}", tx, input)
+            }
+            TransitionError::DuplicateOutput(tx, output) => {
+                write!(
+                    f,
+                    "Transaction {} creates already existing UTXO {}",
+                    tx, output
+                )
+            }
+            TransitionError::InvalidSignature(tx, e) => {
+                write!(f, "Transaction {} has an invalid ring signature: {}", tx, e)
+            }
+            TransitionError::DoubleSpend(tx, key_image) => {
+                write!(f, "Transaction {} reuses key image {}", tx, key_image)
+            }
+            TransitionError::Unbalanced(tx, e) => {
+                write!(f, "Transaction {} is unbalanced: {}", tx, e)
+            }
+            TransitionError::InvalidRangeProof(tx, output, e) => write!(
+                f,
+                "Output {} of transaction {} has an invalid range proof: {}",
+                output, tx, e
+            ),
+            TransitionError::InvalidCommitment(tx, output, e) => write!(
+                f,
+                "Output {} of transaction {} has an invalid commitment: {}",
+                output, tx, e
+            ),
+            TransitionError::RootMismatch(expected, computed) => write!(
+                f,
+                "Block root mismatch, expected {} but computed {}",
+                expected, computed
+            ),
+            TransitionError::EmptyBatch => write!(f, "Batch has no block"),
+            TransitionError::InvalidRoot(root) => write!(f, "Root {} is not 32 bytes hex", root),
+            TransitionError::MerkleError(e) => write!(f, "Merkle error: {}", e),
+            TransitionError::InvalidTransactionHash(claimed, computed) => write!(
+                f,
+                "Transaction hash {} does not match its content, expected {}",
+                claimed, computed
+            ),
+            TransitionError::InvalidUTXOHash(claimed, computed) => write!(
+                f,
+                "UTXO hash {} does not match its content, expected {}",
+                claimed, computed
+            ),
+            TransitionError::InvalidBlock(height, e) => {
+                write!(f, "Block {} is invalid: {}", height, e)
+            }
+            TransitionError::UnexpectedHeight(expected, height) => write!(
+                f,
+                "Block {} found where block {} was expected",
+                height, expected
+            ),
+            TransitionError::BrokenChain(height, parent) => write!(
+                f,
+                "Parent {} of block {} is not the previous block",
+                parent, height
+            ),
+            TransitionError::UTXORootMismatch(height, header, computed) => write!(
+                f,
+                "UTXO root {} of block {} does not match the state, expected {}",
+                header, height, computed
+            ),
+            TransitionError::InvalidExit(tx, output, e) => write!(
+                f,
+                "Exit output {} of transaction {} cannot be withdrawn: {}",
+                output, tx, e
+            ),
+            TransitionError::InvalidEncoding(hash, e) => {
+                write!(f, "Content of {} cannot be hashed: {}", hash, e)
+            }
+            TransitionError::WitnessCount(blocks, witnesses) => {
+                write!(f, "{} witnesses given for {} blocks", witnesses, blocks)
+            }
+            TransitionError::MissingWitness(key) => write!(f, "Missing witness of key {}", key),
+            TransitionError::UnexpectedWitness(key) => {
+                write!(f, "Witness of key {} does not match the transactions", key)
+            }
+            TransitionError::InvalidWitness(key, e) => {
+                write!(f, "Invalid witness of key {}: {}", key, e)
+            }
+            TransitionError::InvalidDeposit(tx, e) => {
+                write!(f, "Deposit {} is invalid: {}", tx, e)
+            }
+            TransitionError::DuplicateDeposit(tx, tx_id) => write!(
+                f,
+                "Deposit {} replays the already applied deposit {}",
+                tx, tx_id
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TransitionError {}
+
+impl State {
+    /// Applies the blocks in turn, see `apply_block`. On error, the blocks before the invalid one
+    /// stay applied.
+    pub fn apply_blocks(&mut self, blocks: &[Block]) -> Result<Vec<BlockWitness>, TransitionError> {
+        blocks
+            .iter()
+            .map(|block| self.apply_block(&block.transactions))
+            .collect()
+    }
+
+    /// Updates the state with the transactions of a block and returns the witness the guest needs
+    /// to follow the updates, see `apply_transactions`. The state is left unchanged on error.
+    ///
+    /// Only the updates of the sets are checked here: signatures, balances and proofs are left to
+    /// `execute`, which the host runs on the resulting `Inputs` before proving them.
+    ///
+    /// # Errors
+    ///
+    /// - When a transaction references a UTXO that is not part of the set.
+    /// - When the signature of a RingCT transaction or one of its key images cannot be decoded.
+    /// - When a key image has already been spent.
+    /// - When a deposit has already been applied, or its id is not 32 bytes hex.
+    /// - When a transaction creates a UTXO that is already part of the set.
+    pub fn apply_block(
+        &mut self,
+        transactions: &[PendingTransaction],
+    ) -> Result<BlockWitness, TransitionError> {
+        let mut state = self.clone();
+        let mut witness = BlockWitness::default();
+        let mut members = BTreeSet::new();
+        for tx in transactions {
+            if let PendingTransaction::PendingDeposit(deposit) = tx {
+                let key = deposit_tree_key(deposit)?;
+                if state.deposit_tree.contains(&key) {
+                    return Err(TransitionError::DuplicateDeposit(
+                        deposit.hash.clone(),
+                        deposit.txId.clone(),
+                    ));
+                }
+                witness
+                    .deposits
+                    .push(state.deposit_tree.update(key, Some(MEMBER_VALUE)));
+            }
+            if let PendingTransaction::PendingRingCTx(ring) = tx {
+                for input in &ring.inputs {
+                    let unknown =
+                        || TransitionError::UnknownInput(ring.hash.clone(), input.clone());
+                    let key = utxo_key(input).map_err(|_| unknown())?;
+                    let member = state.utxos.get(&key).ok_or_else(unknown)?;
+                    if members.insert(key) {
+                        witness.members.push(member.clone());
+                    }
+                    witness
+                        .utxos
+                        .push(state.utxo_tree.update(key, Some(MEMBER_VALUE)));
+                }
+                let signature = decode_signature(ring)
+                    .map_err(|e| TransitionError::InvalidSignature(ring.hash.clone(), e))?;
+                for (column, key_image) in signature.key_images.iter().enumerate() {
+                    let key = key_image_key(key_image).map_err(|_| {
+                        TransitionError::InvalidSignature(
+                            ring.hash.clone(),
+                            MlsagError::InvalidKeyImage(column),
+                        )
+                    })?;
+                    if state.key_image_tree.contains(&key) {
+                        return Err(TransitionError::DoubleSpend(
+                            ring.hash.clone(),
+                            key_image.clone(),
+                        ));
+                    }
+                    witness
+                        .key_images
+                        .push(state.key_image_tree.update(key, Some(MEMBER_VALUE)));
+                }
+            }
+            for output in tx.outputs() {
+                let hash = output.get_hash();
+                let key =
+                    utxo_key(&hash).map_err(|e| TransitionError::MerkleError(e.to_string()))?;
+                if state.utxo_tree.contains(&key) {
+                    return Err(TransitionError::DuplicateOutput(tx.get_hash(), hash));
+                }
+                match output {
+                    UTXO::Exit(_) => witness.utxos.push(state.utxo_tree.update(key, None)),
+                    output => {
+                        witness
+                            .utxos
+                            .push(state.utxo_tree.update(key, Some(MEMBER_VALUE)));
+                        state.utxos.insert(key, output.clone());
+                    }
+                }
+            }
+        }
+        *self = state;
+        Ok(witness)
+    }
+}
+
+/// Root of the block accumulator once the hashes of the new blocks are appended to its frontier.
+pub fn block_root(
+    frontier: &MerkleAccumulator,
+    blocks_hash: &[String],
+) -> Result<String, TransitionError> {
+    let mut accumulator = frontier.clone();
+    for block_hash in blocks_hash {
+        parse_hash(block_hash)
+            .and_then(|leaf| accumulator.append(leaf))
+            .map_err(|e| TransitionError::MerkleError(e.to_string()))?;
+    }
+    accumulator_root(&accumulator)
+}
+
+fn accumulator_root(accumulator: &MerkleAccumulator) -> Result<String, TransitionError> {
+    accumulator
+        .root()
+        .map(hex::encode)
+        .map_err(|e| TransitionError::MerkleError(e.to_string()))
+}
+
+/// Runs the whole transition described by the input and returns the journal proving it.
+///
+/// The frontier must be the one of the block root at t, the blocks must extend the chain it
+/// commits to, and appending their hashes to it must give `state_t_1`, so only the new blocks are
+/// hashed whatever the history. Both roots are committed, `state_t` as `prevBatchHash`, so the
+/// contract only accepts a batch extending the blocks it holds. The transactions of each block
+/// are applied in turn on top of the state at t, between the UTXO roots of its header, with the
+/// witness of the updates of the state trees produced by `State::apply_block`. The deposits of
+/// the batch are committed through their hash and its exit outputs through the root of their
+/// withdrawal tree. Guest and host run the very same checks, so that the host can refuse an
+/// invalid transition before proving it.
+pub fn execute(input: &Inputs) -> Result<StateTransitionJournal, TransitionError> {
+    let from_block = input.from_block().ok_or(TransitionError::EmptyBatch)?;
+    let to_block = input.to_block().ok_or(TransitionError::EmptyBatch)?;
+    let frontier_root = accumulator_root(&input.block_frontier)?;
+    if frontier_root != input.state_t {
+        return Err(TransitionError::RootMismatch(
+            input.state_t.clone(),
+            frontier_root,
+        ));
+    }
+    let blocks_hash = verify_chain(&input.block_frontier, &input.parent_proof, &input.blocks)?;
+    let computed = block_root(&input.block_frontier, &blocks_hash)?;
+    if computed != input.state_t_1 {
+        return Err(TransitionError::RootMismatch(
+            input.state_t_1.clone(),
+            computed,
+        ));
+    }
+
+    if input.witnesses.len() != input.blocks.len() {
+        return Err(TransitionError::WitnessCount(
+            input.blocks.len(),
+            input.witnesses.len(),
+        ));
+    }
+    let mut state = input.state.clone();
+    let generators = Generators::new(); // shared by the range proofs of every block
+    for (block, witness) in input.blocks.iter().zip(&input.witnesses) {
+        let header = &block.header;
+        check_utxo_root(header.height, &header.pre_utxo_root, &state.utxo_root)?;
+        state = apply_transactions(&generators, &state, &block.transactions, witness)?;
+        check_utxo_root(header.height, &header.post_utxo_root, &state.utxo_root)?;
+    }
+    let deposits = batch_deposits(&input.blocks)?;
+    let withdrawals = batch_withdrawals(&input.blocks)?;
+
+    Ok(StateTransitionJournal {
+        prevRoot: state_root(&input.state)?,
+        newRoot: state_root(&state)?,
+        fromBlock: from_block,
+        toBlock: to_block,
+        prevBatchHash: bytes32(&input.state_t)?,
+        batchHash: bytes32(&input.state_t_1)?,
+        depositHash: B256::from(deposit_hash(&deposits)),
+        withdrawalRoot: B256::from(withdrawal_root(&withdrawals)),
+    })
+}
+
+/// Deposits of the blocks, in order.
+pub fn batch_deposits(blocks: &[Block]) -> Result<Vec<Deposit>, TransitionError> {
+    let mut deposits = Vec::new();
+    for tx in blocks.iter().flat_map(|block| &block.transactions) {
+        if let PendingTransaction::PendingDeposit(deposit) = tx {
+            deposits.push(
+                Deposit::from_deposit_tx(deposit)
+                    .map_err(|e| TransitionError::InvalidDeposit(deposit.hash.clone(), e))?,
+            );
+        }
+    }
+    Ok(deposits)
+}
+
+/// Withdrawals of the exit outputs of the blocks, in order.
+///
+/// An exit output is only proven absent from the set, which it never enters, so the same exit
+/// is rejected here, whether it is repeated within a block or across the blocks of the batch.
+pub fn batch_withdrawals(blocks: &[Block]) -> Result<Vec<Withdrawal>, TransitionError> {
+    let mut withdrawals = Vec::new();
+    let mut exits = BTreeSet::new();
+    for tx in blocks.iter().flat_map(|block| &block.transactions) {
+        for output in tx.outputs() {
+            if let UTXO::Exit(exit) = output {
+                let withdrawal = Withdrawal::from_exit(exit).map_err(|e| {
+                    TransitionError::InvalidExit(tx.get_hash(), exit.hash.clone(), e)
+                })?;
+                if !exits.insert(withdrawal.utxoHash) {
+                    return Err(TransitionError::DuplicateOutput(
+                        tx.get_hash(),
+                        exit.hash.clone(),
+                    ));
+                }
+                withdrawals.push(withdrawal);
+            }
+        }
+    }
+    Ok(withdrawals)
+}
+
+/// Hashes of the blocks, once checked that they extend the chain committed to by the frontier.
+///
+/// Heights follow each other from the number of blocks of the frontier plus one. The first block
+/// points to the last block of the frontier, proven by `parent_proof`, or to the zero hash when
+/// the frontier is empty, and every other block to the one before it. The transaction root of
+/// each header must match the transactions of its block.
+pub fn verify_chain(
+    frontier: &MerkleAccumulator,
+    parent_proof: &[Hash],
+    blocks: &[Block],
+) -> Result<Vec<String>, TransitionError> {
+    let mut parent: Option<B256> = None;
+    let mut hashes = Vec::with_capacity(blocks.len());
+    for (height, block) in (frontier.count() + 1..).zip(blocks) {
+        let header = &block.header;
+        if header.height != height {
+            return Err(TransitionError::UnexpectedHeight(height, header.height));
+        }
+        block
+            .verify_tx_root()
+            .map_err(|e| TransitionError::InvalidBlock(height, e))?;
+        let parent_hash = bytes32(&header.parent_hash)?;
+        let linked = match parent {
+            Some(parent) => parent == parent_hash,
+            None if frontier.count() == 0 => parent_hash == B256::ZERO,
+            None => frontier.is_last_leaf(&parent_hash.0, parent_proof),
+        };
+        if !linked {
+            return Err(TransitionError::BrokenChain(
+                height,
+                header.parent_hash.clone(),
+            ));
+        }
+        let hash = block
+            .hash()
+            .map_err(|e| TransitionError::InvalidBlock(height, e))?;
+        parent = Some(bytes32(&hash)?);
+        hashes.push(hash);
+    }
+    Ok(hashes)
+}
+
+fn check_utxo_root(height: u64, header: &str, computed: &str) -> Result<(), TransitionError> {
+    if bytes32(header)? != bytes32(computed)? {
+        return Err(TransitionError::UTXORootMismatch(
+            height,
+            header.to_string(),
+            computed.to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn state_root(commitment: &StateCommitment) -> Result<B256, TransitionError> {
+    commitment
+        .root()
+        .map(B256::from)
+        .map_err(|e| TransitionError::MerkleError(e.to_string()))
+}
+
+fn bytes32(root: &str) -> Result<B256, TransitionError> {
+    root_to_bytes32(root).ok_or_else(|| TransitionError::InvalidRoot(root.to_string()))
+}
+
+/// Applies the ordered transactions on top of the state committed to by `state_t` and returns the
+/// commitment of the resulting state.
+///
+/// The state trees are only known through their roots, every update goes through the next
+/// witness of `witness`, which must be the update the transaction makes. Every input of a RingCT
+/// transaction is a ring member, its content is taken from `witness.members` and the witness
+/// proves it part of the set, and the ring signature must be valid for these members. Its key
+/// images are added to the spent set, each witness proving the key image absent before inserting
+/// it, which rejects any reuse, against history or within the batch. Its pseudo commitments must
+/// balance its output commitments and fee. The output of a deposit must be a coinbase committing
+/// to its clear amount, and its root chain transaction id is added to the applied deposits the
+/// same way as a key image, so a deposit is never applied twice. Outputs of deposits and RingCT
+/// transactions are inserted in the set, coinbase outputs of a RingCT transaction must commit to
+/// their clear amount too. Exit outputs must commit to their clear amount and leave the set,
+/// they are withdrawn on the chain they exit to, their witness only proves them absent from it.
+/// A repeated exit is rejected by `batch_withdrawals`, over the whole batch.
+///
+/// # Errors
+///
+/// - When a transaction references a UTXO that is not part of the set.
+/// - When the ring signature of a RingCT transaction does not verify.
+/// - When a key image has already been spent.
+/// - When the output of a deposit is not a coinbase committing to its amount, or the deposit has
+///   already been applied.
+/// - When the witnesses do not match the updates of the transactions.
+/// - When a RingCT transaction creates value.
+/// - When the range proof of a payment output does not verify.
+/// - When the commitment of a coinbase or exit output does not open to its amount.
+/// - When an exit output cannot be withdrawn.
+/// - When a transaction creates a UTXO that is already part of the set.
+/// - When the hash of a transaction or of a UTXO does not match its content.
+pub fn apply_transactions(
+    generators: &Generators,
+    state_t: &StateCommitment,
+    transactions: &[PendingTransaction],
+    witness: &BlockWitness,
+) -> Result<StateCommitment, TransitionError> {
+    verify_hashes(&witness.members, transactions)?;
+    verify_range_proofs(generators, transactions)?;
+
+    let members: BTreeMap<String, &UTXO> = witness
+        .members
+        .iter()
+        .map(|utxo| (utxo.get_hash(), utxo))
+        .collect();
+    let mut utxo_root = bytes32(&state_t.utxo_root)?.0;
+    let mut key_image_root = bytes32(&state_t.key_image_root)?.0;
+    let mut deposit_root = bytes32(&state_t.deposit_root)?.0;
+    let mut utxo_witnesses = witness.utxos.iter();
+    let mut key_image_witnesses = witness.key_images.iter();
+    let mut deposit_witnesses = witness.deposits.iter();
+
+    for tx in transactions {
+        if let PendingTransaction::PendingDeposit(deposit) = tx {
+            Deposit::from_deposit_tx(deposit)
+                .map_err(|e| TransitionError::InvalidDeposit(deposit.hash.clone(), e))?;
+            let key = deposit_tree_key(deposit)?;
+            deposit_root = apply_witness(
+                &mut deposit_witnesses,
+                &deposit_root,
+                &key,
+                None,
+                Some(MEMBER_VALUE),
+            )?
+            .ok_or_else(|| {
+                TransitionError::DuplicateDeposit(deposit.hash.clone(), deposit.txId.clone())
+            })?;
+        }
+        if let PendingTransaction::PendingRingCTx(ring) = tx {
+            let mut ring_members = Vec::new();
+            for input in &ring.inputs {
+                let unknown = || TransitionError::UnknownInput(ring.hash.clone(), input.clone());
+                let member = members.get(input).ok_or_else(unknown)?;
+                let key = utxo_key(input).map_err(|_| unknown())?;
+                utxo_root = apply_witness(
+                    &mut utxo_witnesses,
+                    &utxo_root,
+                    &key,
+                    Some(MEMBER_VALUE),
+                    Some(MEMBER_VALUE),
+                )?
+                .ok_or_else(unknown)?;
+                ring_members.push(*member);
+            }
+            let signature = verify_ring_signature(ring, &ring_members)
+                .map_err(|e| TransitionError::InvalidSignature(ring.hash.clone(), e))?;
+            for (column, key_image) in signature.key_images.iter().enumerate() {
+                let key = key_image_key(key_image).map_err(|_| {
+                    TransitionError::InvalidSignature(
+                        ring.hash.clone(),
+                        MlsagError::InvalidKeyImage(column),
+                    )
+                })?;
+                key_image_root = apply_witness(
+                    &mut key_image_witnesses,
+                    &key_image_root,
+                    &key,
+                    None,
+                    Some(MEMBER_VALUE),
+                )?
+                .ok_or_else(|| {
+                    TransitionError::DoubleSpend(ring.hash.clone(), key_image.clone())
+                })?;
+            }
+            ring.verify_balance()
+                .map_err(|e| TransitionError::Unbalanced(ring.hash.clone(), e))?;
+        }
+        for output in tx.outputs() {
+            let clear = match output {
+                // the output of a deposit is checked with the deposit
+                _ if matches!(tx, PendingTransaction::PendingDeposit(_)) => None,
+                UTXO::Coinbase(CoinbaseUTXO {
+                    commitment, amount, ..
+                })
+                | UTXO::Exit(ExitUTXO {
+                    commitment, amount, ..
+                }) => Some((commitment, amount)),
+                UTXO::Payment(_) => None,
+            };
+            if let Some((commitment, amount)) = clear {
+                verify_clear_commitment(commitment, amount).map_err(|e| {
+                    TransitionError::InvalidCommitment(tx.get_hash(), output.get_hash(), e)
+                })?;
+            }
+            let hash = output.get_hash();
+            let duplicate = || TransitionError::DuplicateOutput(tx.get_hash(), hash.clone());
+            let new_value = match output {
+                UTXO::Exit(exit) => {
+                    Withdrawal::from_exit(exit).map_err(|e| {
+                        TransitionError::InvalidExit(tx.get_hash(), exit.hash.clone(), e)
+                    })?;
+                    None
+                }
+                _ => Some(MEMBER_VALUE),
+            };
+            let key = utxo_key(&hash).map_err(|e| TransitionError::MerkleError(e.to_string()))?;
+            utxo_root = apply_witness(&mut utxo_witnesses, &utxo_root, &key, None, new_value)?
+                .ok_or_else(duplicate)?;
+        }
+    }
+    let left = utxo_witnesses
+        .next()
+        .or(key_image_witnesses.next())
+        .or(deposit_witnesses.next());
+    if let Some(witness) = left {
+        return Err(TransitionError::UnexpectedWitness(hex::encode(witness.key)));
+    }
+    Ok(StateCommitment {
+        utxo_root: hex::encode(utxo_root),
+        key_image_root: hex::encode(key_image_root),
+        deposit_root: hex::encode(deposit_root),
+    })
+}
+
+fn deposit_tree_key(deposit: &PendingUserDepositTx) -> Result<Hash, TransitionError> {
+    deposit_key(&deposit.txId).map_err(|_| {
+        let e = EncodingError::InvalidHex("txId", deposit.txId.clone());
+        TransitionError::InvalidDeposit(deposit.hash.clone(), DepositError::InvalidEncoding(e))
+    })
+}
+
+/// Applies the next witness, which must update `key` from `old_value` to `new_value`, to `root`.
+///
+/// Returns `None` when the old value is not the one committed to by `root`.
+fn apply_witness<'a>(
+    witnesses: &mut impl Iterator<Item = &'a SparseWitness>,
+    root: &Hash,
+    key: &Hash,
+    old_value: Option<Hash>,
+    new_value: Option<Hash>,
+) -> Result<Option<Hash>, TransitionError> {
+    let witness = witnesses
+        .next()
+        .ok_or_else(|| TransitionError::MissingWitness(hex::encode(key)))?;
+    if witness.key != *key || witness.old_value != old_value || witness.new_value != new_value {
+        return Err(TransitionError::UnexpectedWitness(hex::encode(witness.key)));
+    }
+    match witness.apply(root) {
+        Ok(root) => Ok(Some(root)),
+        Err(MerkleError::RootMismatch(_, _)) => Ok(None),
+        Err(e) => Err(TransitionError::InvalidWitness(hex::encode(key), e)),
+    }
+}
+
+/// Recomputes the hash of every ring member, of every transaction and of their outputs.
+///
+/// The hashes are the keys of the UTXO tree and the messages of the ring signatures, so none of
+/// them is taken as given: a UTXO whose content does not match its hash could spend or create
+/// anything under the key of another one.
+pub fn verify_hashes(
+    members: &[UTXO],
+    transactions: &[PendingTransaction],
+) -> Result<(), TransitionError> {
+    let outputs = transactions.iter().flat_map(|tx| tx.outputs());
+    for utxo in members.iter().chain(outputs) {
+        let computed = utxo
+            .utxo_hash()
+            .map_err(|e| TransitionError::InvalidEncoding(utxo.get_hash(), e))?;
+        if utxo.get_hash() != computed {
+            return Err(TransitionError::InvalidUTXOHash(utxo.get_hash(), computed));
+        }
+    }
+    for tx in transactions {
+        let computed = tx
+            .tx_hash()
+            .map_err(|e| TransitionError::InvalidEncoding(tx.get_hash(), e))?;
+        if tx.get_hash() != computed {
+            return Err(TransitionError::InvalidTransactionHash(
+                tx.get_hash(),
+                computed,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Batch verifies the range proofs of every payment output created by the transactions.
+pub fn verify_range_proofs(
+    generators: &Generators,
+    transactions: &[PendingTransaction],
+) -> Result<(), TransitionError> {
+    let mut outputs = Vec::new();
+    for tx in transactions {
+        if let PendingTransaction::PendingRingCTx(ring) = tx {
+            for output in &ring.outputs {
+                if let UTXO::Payment(payment) = output {
+                    outputs.push((ring.hash.as_str(), payment));
+                }
+            }
+        }
+    }
+    let proofs: Vec<_> = outputs
+        .iter()
+        .map(|(_, payment)| (&payment.rangeProof, payment.commitment.as_str()))
+        .collect();
+    verify_batch(generators, &proofs).map_err(|e| match e.proof_index() {
+        Some(index) => TransitionError::InvalidRangeProof(
+            outputs[index].0.to_string(),
+            outputs[index].1.hash.clone(),
+            e,
+        ),
+        None => TransitionError::InvalidRangeProof(String::new(), String::new(), e),
+    })
+}
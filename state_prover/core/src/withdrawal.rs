@@ -0,0 +1,91 @@
+//! Withdrawals of the exit outputs of a batch.
+//!
+//! Every exit output created by the batch is a leaf of a `StandardMerkleTree` whose root is
+//! committed in the journal, the tree `StandardMerkleTree.of` builds in JavaScript over
+//! `(bytes32 utxoHash, address recipient, uint256 amount, string currency, string exitChain)`,
+//! so the bridge checks a claim with `MerkleProof.verify(proof, withdrawalRoot, leaf)` where
+//! `leaf = keccak256(bytes.concat(keccak256(abi.encode(utxoHash, recipient, amount, currency,
+//! exitChain))))`, and keeps the `utxoHash` of every claim paid. The recipient is the Ethereum
+//! address of the one-time public key of the output, only its owner can sign for it. The amount
+//! of the leaf is in wei, the clear amount of the exit times `AMOUNT_UNIT_WEI`.
+use crate::crypto::{amount_to_wei, parse_amount, point_from_hex, point_to_address};
+use crate::interface::ExitUTXO;
+use crate::merkle::{parse_hash, Hash, Leaf, StandardMerkleTree};
+use alloc::string::String;
+use alloy_primitives::{Address, B256};
+use alloy_sol_types::{sol, SolValue};
+use core::fmt::Display;
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Withdrawal {
+        bytes32 utxoHash;
+        address recipient;
+        uint256 amount;
+        string currency;
+        string exitChain;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WithdrawalError {
+    InvalidHash(String),
+    InvalidRecipient(String), // public key
+    InvalidAmount(String),
+}
+
+impl Display for WithdrawalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            WithdrawalError::InvalidHash(hash) => {
+                write!(f, "Exit hash {} is not 32 bytes hex", hash)
+            }
+            WithdrawalError::InvalidRecipient(key) => {
+                write!(f, "Exit public key {} is not a point", key)
+            }
+            WithdrawalError::InvalidAmount(amount) => {
+                write!(f, "Exit amount {} is not a clear amount", amount)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WithdrawalError {}
+
+impl Withdrawal {
+    /// Withdrawal of an exit output, whose commitment must open to its clear amount.
+    pub fn from_exit(exit: &ExitUTXO) -> Result<Self, WithdrawalError> {
+        let utxo_hash =
+            parse_hash(&exit.hash).map_err(|_| WithdrawalError::InvalidHash(exit.hash.clone()))?;
+        let public_key = point_from_hex(&exit.public_key)
+            .map_err(|_| WithdrawalError::InvalidRecipient(exit.public_key.clone()))?;
+        let amount = parse_amount(&exit.amount)
+            .map_err(|_| WithdrawalError::InvalidAmount(exit.amount.clone()))?;
+        Ok(Withdrawal {
+            utxoHash: B256::from(utxo_hash),
+            recipient: Address::from(point_to_address(&public_key)),
+            amount: amount_to_wei(amount),
+            currency: exit.currency.clone(),
+            exitChain: exit.exitChain.clone(),
+        })
+    }
+
+    pub fn leaf(&self) -> Leaf {
+        Leaf::Standard(self.abi_encode_params())
+    }
+
+    /// Hash of the leaf of the withdrawal in the tree.
+    pub fn leaf_hash(&self) -> Hash {
+        self.leaf().hash().expect("Standard leaves always hash")
+    }
+}
+
+pub fn withdrawal_tree(withdrawals: &[Withdrawal]) -> StandardMerkleTree {
+    StandardMerkleTree::new(withdrawals.iter().map(Withdrawal::leaf_hash).collect())
+}
+
+/// Root of the withdrawal tree, zero for a batch without exit.
+pub fn withdrawal_root(withdrawals: &[Withdrawal]) -> Hash {
+    withdrawal_tree(withdrawals).root().unwrap_or([0u8; 32])
+}
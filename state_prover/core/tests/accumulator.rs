@@ -0,0 +1,153 @@
+use state_prover_core::{keccak256, Hash, MerkleAccumulator, MerkleError, ACCUMULATOR_DEPTH};
+
+// Roots of `leaves(count)` for each of `COUNTS`, computed over the whole padded tree by
+// tests/vectors/gen_vectors.py. The root of the empty accumulator is the `state_t` of the first
+// batch.
+const COUNTS: [usize; 7] = [0, 1, 2, 3, 5, 8, 13];
+const ROOTS: [&str; 7] = [
+    "a116e19a7984f21055d07b606c55628a5ffbf8ae1261c1e9f4e3a61620cf810a",
+    "a93ae40b8e20134aee734414b9424f5e0e86eeeb10ce4d9cc572ca1eb438c3bc",
+    "6d09049ba3e464bdbd1243f82dc4bae01a11447f2c43ad649cdcf98941c4fe76",
+    "d229c6bf43816c84b61673d8fab99f7f1863ac27c8adf9ec55af9dd2bf9d7533",
+    "4d6cc740f7e569fda5b94ce6be506456bccbd684da480a950c2a1ee5ae4932aa",
+    "2309a434698f5a09e1fdf59173ff15e6aec12a42a53202803d3cff77e8ab3149",
+    "33d3ed6e0edf44dca087eebb53ea068ba28e48f9fd36a3de5bc957874983f842",
+];
+
+/// Deterministic leaves, the same as tests/vectors/gen_vectors.py.
+fn leaves(count: usize) -> Vec<Hash> {
+    (0..count as u64)
+        .map(|i| keccak256(&i.to_be_bytes()))
+        .collect()
+}
+
+fn hash_ordered(left: &Hash, right: &Hash) -> Hash {
+    keccak256(&[left.as_slice(), right.as_slice()].concat())
+}
+
+/// Root of the full tree of depth `ACCUMULATOR_DEPTH`, hashed level by level without a frontier.
+fn full_tree_root(leaves: &[Hash]) -> Hash {
+    let mut level = leaves.to_vec();
+    let mut zero = [0u8; 32];
+    for _ in 0..ACCUMULATOR_DEPTH {
+        if level.len() % 2 == 1 {
+            level.push(zero);
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| hash_ordered(&pair[0], &pair[1]))
+            .collect();
+        zero = hash_ordered(&zero, &zero);
+    }
+    let node = level.first().copied().unwrap_or(zero);
+    let mut count = [0u8; 32];
+    count[24..].copy_from_slice(&(leaves.len() as u64).to_be_bytes());
+    hash_ordered(&node, &count)
+}
+
+fn accumulator(leaves: &[Hash]) -> MerkleAccumulator {
+    let mut accumulator = MerkleAccumulator::new();
+    for leaf in leaves {
+        accumulator.append(*leaf).unwrap();
+    }
+    accumulator
+}
+
+#[test]
+fn golden_roots() {
+    for (count, root) in COUNTS.into_iter().zip(ROOTS) {
+        let accumulator = accumulator(&leaves(count));
+        assert_eq!(accumulator.count(), count as u64);
+        assert_eq!(hex::encode(accumulator.root().unwrap()), root);
+    }
+}
+
+#[test]
+fn frontier_appends_match_the_full_tree() {
+    let leaves = leaves(70);
+    let mut accumulator = MerkleAccumulator::new();
+    for count in 0..=leaves.len() {
+        assert_eq!(
+            accumulator.root().unwrap(),
+            full_tree_root(&leaves[..count]),
+            "{} leaves",
+            count
+        );
+        // one peak per one bit of the count
+        assert_eq!(accumulator.peaks().len(), count.count_ones() as usize);
+        if count < leaves.len() {
+            accumulator.append(leaves[count]).unwrap();
+        }
+    }
+}
+
+#[test]
+fn frontier_resumes_the_accumulator() {
+    let leaves = leaves(21);
+    let whole = accumulator(&leaves);
+    let mut resumed =
+        MerkleAccumulator::from_frontier(13, accumulator(&leaves[..13]).peaks().to_vec()).unwrap();
+    for leaf in &leaves[13..] {
+        resumed.append(*leaf).unwrap();
+    }
+    assert_eq!(resumed, whole);
+    assert_eq!(resumed.root(), whole.root());
+}
+
+#[test]
+fn rejects_invalid_frontiers() {
+    // 13 leaves need three peaks
+    let peaks = accumulator(&leaves(13)).peaks().to_vec();
+    assert_eq!(
+        MerkleAccumulator::from_frontier(12, peaks.clone()),
+        Err(MerkleError::InvalidFrontier(12, 3))
+    );
+    assert_eq!(
+        MerkleAccumulator::from_frontier(13, peaks[1..].to_vec()),
+        Err(MerkleError::InvalidFrontier(13, 2))
+    );
+    assert_eq!(
+        MerkleAccumulator::from_frontier(1 << ACCUMULATOR_DEPTH, vec![[0u8; 32]]),
+        Err(MerkleError::InvalidFrontier(1 << ACCUMULATOR_DEPTH, 1))
+    );
+
+    // a deserialized frontier is checked before use, bincode encodes the fields in order
+    let invalid: MerkleAccumulator =
+        bincode::deserialize(&bincode::serialize(&(2u64, Vec::<Hash>::new())).unwrap()).unwrap();
+    assert_eq!(invalid.root(), Err(MerkleError::InvalidFrontier(2, 0)));
+    let mut appended = invalid.clone();
+    assert_eq!(
+        appended.append([0u8; 32]),
+        Err(MerkleError::InvalidFrontier(2, 0))
+    );
+    assert!(!invalid.is_last_leaf(&[0u8; 32], &[]));
+}
+
+#[test]
+fn last_leaf_proofs() {
+    let leaves = leaves(40);
+    for count in 1..=leaves.len() {
+        let accumulator = accumulator(&leaves[..count]);
+        let last = &leaves[count - 1];
+        let proof = MerkleAccumulator::last_leaf_proof(&leaves[..count]);
+        // the proof spans the lowest peak, empty when the count is odd
+        assert_eq!(proof.len(), count.trailing_zeros() as usize);
+        assert!(accumulator.is_last_leaf(last, &proof), "{} leaves", count);
+
+        // no other leaf is the last one, and every sibling counts
+        if count > 1 {
+            assert!(!accumulator.is_last_leaf(&leaves[count - 2], &proof));
+        }
+        for position in 0..proof.len() {
+            let mut tampered = proof.clone();
+            tampered[position][0] ^= 1;
+            assert!(!accumulator.is_last_leaf(last, &tampered));
+        }
+        let mut longer = proof.clone();
+        longer.push(leaves[0]);
+        assert!(!accumulator.is_last_leaf(last, &longer));
+    }
+
+    assert!(MerkleAccumulator::last_leaf_proof(&[]).is_empty());
+    assert!(!MerkleAccumulator::new().is_last_leaf(&leaves[0], &[]));
+}
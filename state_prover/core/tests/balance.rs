@@ -0,0 +1,179 @@
+mod common;
+
+use common::ringct::{ring_ct, ring_ct_ring, set_signature, sign};
+use common::{deposit, genesis, withdrawal};
+use k256::Scalar;
+use state_prover_core::transition::TransitionError;
+use state_prover_core::{
+    commit, hash_to_scalar, point_to_hex, verify_balance, BalanceError, MlsagError, PendingRingCT,
+    PendingTransaction, StateCommitment, UTXO,
+};
+
+fn secret(label: &str) -> Scalar {
+    hash_to_scalar(label.as_bytes())
+}
+
+fn apply(utxo_set: &[UTXO], tx: PendingRingCT) -> Result<StateCommitment, TransitionError> {
+    common::apply(
+        &mut genesis(utxo_set),
+        &[PendingTransaction::PendingRingCTx(tx)],
+    )
+}
+
+#[test]
+fn spends_the_value_of_the_signer() {
+    let members = [deposit("mallory", 1), deposit("victim", 1_000_000)];
+    let tx = ring_ct(
+        &members,
+        0,
+        &secret("mallory"),
+        &Scalar::ZERO,
+        &commit(1, &Scalar::ZERO),
+        vec![withdrawal("mallory", 1)],
+        0,
+    );
+    assert!(apply(&members, tx).is_ok());
+}
+
+#[test]
+fn pseudo_commitment_is_bound_to_the_spent_input() {
+    // Mallory rings her 1 unit coinbase with a 1 000 000 units UTXO of someone else and claims
+    // the large amount in the pseudo commitment: the balance holds, but the commitment column of
+    // her row is (1 - 1 000 000) * H, whose discrete logarithm she does not know.
+    let members = [deposit("mallory", 1), deposit("victim", 1_000_000)];
+    let pseudo = commit(1_000_000, &Scalar::ZERO);
+    let tx = ring_ct(
+        &members,
+        0,
+        &secret("mallory"),
+        &Scalar::ZERO,
+        &pseudo,
+        vec![withdrawal("mallory", 1_000_000)],
+        0,
+    );
+    assert_eq!(tx.verify_balance(), Ok(()));
+    assert!(matches!(
+        apply(&members, tx),
+        Err(TransitionError::InvalidSignature(
+            _,
+            MlsagError::RingNotClosed(1)
+        ))
+    ));
+
+    // a signature over the public keys only is no longer accepted
+    let mut tx = ring_ct(
+        &members,
+        0,
+        &secret("mallory"),
+        &Scalar::ZERO,
+        &pseudo,
+        vec![withdrawal("mallory", 1_000_000)],
+        0,
+    );
+    let ring: Vec<_> = ring_ct_ring(&members, &pseudo)
+        .into_iter()
+        .map(|row| vec![row[0]])
+        .collect();
+    let signature = sign(&tx.hash, &ring, 0, &[secret("mallory")], 1);
+    set_signature(&mut tx, &signature);
+    assert!(matches!(
+        apply(&members, tx),
+        Err(TransitionError::InvalidSignature(
+            _,
+            MlsagError::ColumnCount(1)
+        ))
+    ));
+
+    // neither is a pseudo commitment per ring row, the rows of the decoys being unconstrained
+    let mut tx = ring_ct(
+        &members,
+        0,
+        &secret("mallory"),
+        &Scalar::ZERO,
+        &commit(1, &Scalar::ZERO),
+        vec![withdrawal("mallory", 1_000_000)],
+        0,
+    );
+    tx.pseudo_commitments
+        .push(point_to_hex(&commit(999_999, &Scalar::ZERO)));
+    tx.hash = tx.tx_hash().unwrap();
+    let ring = ring_ct_ring(&members, &commit(1, &Scalar::ZERO));
+    let signature = sign(&tx.hash, &ring, 0, &[secret("mallory"), Scalar::ZERO], 1);
+    set_signature(&mut tx, &signature);
+    assert!(matches!(
+        apply(&members, tx),
+        Err(TransitionError::InvalidSignature(
+            _,
+            MlsagError::PseudoCommitmentCount(2)
+        ))
+    ));
+}
+
+#[test]
+fn rejects_unbalanced() {
+    let members = [deposit("alice", 10), deposit("bob", 20)];
+    let tx = ring_ct(
+        &members,
+        1,
+        &secret("bob"),
+        &Scalar::ZERO,
+        &commit(20, &Scalar::ZERO),
+        vec![withdrawal("dave", 19)],
+        0,
+    );
+    assert!(matches!(
+        apply(&members, tx),
+        Err(TransitionError::Unbalanced(_, BalanceError::Imbalance(_)))
+    ));
+
+    // the fee makes up for the difference
+    let tx = ring_ct(
+        &members,
+        1,
+        &secret("bob"),
+        &Scalar::ZERO,
+        &commit(20, &Scalar::ZERO),
+        vec![withdrawal("dave", 19)],
+        1,
+    );
+    assert!(apply(&members, tx).is_ok());
+}
+
+#[test]
+fn verify_balance_checks_the_commitments() {
+    let outputs = [
+        point_to_hex(&commit(7, &Scalar::ONE)),
+        point_to_hex(&commit(2, &-Scalar::ONE)),
+    ];
+    let pseudo = point_to_hex(&commit(10, &Scalar::ZERO));
+    assert_eq!(verify_balance(&pseudo, &outputs, 1), Ok(()));
+    assert!(matches!(
+        verify_balance(&pseudo, &outputs, 0),
+        Err(BalanceError::Imbalance(_))
+    ));
+    assert_eq!(
+        verify_balance("02aa", &outputs, 1),
+        Err(BalanceError::InvalidPseudoCommitment)
+    );
+    assert_eq!(
+        verify_balance(&pseudo, &[outputs[0].clone(), "zz".to_string()], 1),
+        Err(BalanceError::InvalidOutputCommitment(1))
+    );
+
+    let members = [deposit("alice", 10)];
+    let mut tx = ring_ct(
+        &members,
+        0,
+        &secret("alice"),
+        &Scalar::ZERO,
+        &commit(10, &Scalar::ZERO),
+        vec![withdrawal("dave", 10)],
+        0,
+    );
+    assert_eq!(tx.verify_balance(), Ok(()));
+    tx.pseudo_commitments.clear();
+    assert_eq!(
+        tx.verify_balance(),
+        Err(BalanceError::PseudoCommitmentCount(0))
+    );
+}
@@ -0,0 +1,321 @@
+mod common;
+
+use common::deposit_tx;
+use state_prover_core::transition::{
+    block_root, execute, verify_chain, TransitionError, EMPTY_ROOT,
+};
+use state_prover_core::{
+    keccak256, parse_hash, transactions_root, Block, BlockError, BlockHeader, BlockWitness, Hash,
+    Inputs, MerkleAccumulator, PendingTransaction, State, StateCommitment,
+};
+
+// Hash of `header()` and transaction root of `labelled(3)`, computed by
+// tests/vectors/gen_vectors.py.
+const HEADER_HASH: &str = "f55f870ca247cf697c6b9c6bc3686e8e16838a98afd2577be8c7584b5543cad2";
+const TX_ROOT: &str = "01f93d3a165539d09a49cad6d3ab9ef17b98d3b30d15b7ba6e7aa4b4196c513a";
+
+const SEQUENCER: &str = "0x00000000000000000000000000000000000000a1";
+
+fn hash(label: &str) -> String {
+    hex::encode(keccak256(label.as_bytes()))
+}
+
+fn header() -> BlockHeader {
+    BlockHeader {
+        height: 2,
+        parent_hash: hash("parent"),
+        timestamp: 1700000001,
+        tx_root: hash("tx root"),
+        pre_utxo_root: hash("pre utxo root"),
+        post_utxo_root: hash("post utxo root"),
+        sequencer: SEQUENCER.to_string(),
+    }
+}
+
+/// Deposits whose hashes are relabelled `keccak256("tx <i>")`, only their hashes are read.
+fn labelled(count: usize) -> Vec<PendingTransaction> {
+    (0..count)
+        .map(|i| match deposit_tx(&format!("user {}", i), 10) {
+            PendingTransaction::PendingDeposit(mut deposit) => {
+                deposit.hash = hash(&format!("tx {}", i));
+                PendingTransaction::PendingDeposit(deposit)
+            }
+            tx => tx,
+        })
+        .collect()
+}
+
+/// Block of `transactions` at `height`, with its transaction root and empty UTXO roots.
+fn block(height: u64, parent_hash: &str, transactions: Vec<PendingTransaction>) -> Block {
+    Block {
+        header: BlockHeader {
+            height,
+            parent_hash: parent_hash.to_string(),
+            timestamp: 1700000000 + height,
+            tx_root: transactions_root(&transactions).unwrap(),
+            pre_utxo_root: EMPTY_ROOT.to_string(),
+            post_utxo_root: EMPTY_ROOT.to_string(),
+            sequencer: SEQUENCER.to_string(),
+        },
+        transactions,
+    }
+}
+
+/// Chain of one block per batch of transactions from the genesis state, each header holding the
+/// UTXO roots of the host state around its transactions, with the witnesses of the blocks.
+fn chain(batches: Vec<Vec<PendingTransaction>>) -> (Vec<Block>, Vec<BlockWitness>) {
+    let mut state = State::new();
+    let mut parent = EMPTY_ROOT.to_string();
+    let mut blocks = Vec::new();
+    let mut witnesses = Vec::new();
+    for (height, transactions) in (1..).zip(batches) {
+        let mut block = block(height, &parent, transactions);
+        block.header.pre_utxo_root = state.commitment().utxo_root;
+        witnesses.push(state.apply_block(&block.transactions).unwrap());
+        block.header.post_utxo_root = state.commitment().utxo_root;
+        parent = block.hash().unwrap();
+        blocks.push(block);
+    }
+    (blocks, witnesses)
+}
+
+/// Inputs of the first batch, from the empty chain and the genesis state.
+fn inputs(blocks: Vec<Block>, witnesses: Vec<BlockWitness>) -> Inputs {
+    let frontier = MerkleAccumulator::new();
+    let hashes: Vec<String> = blocks.iter().map(|block| block.hash().unwrap()).collect();
+    Inputs {
+        state_t_1: block_root(&frontier, &hashes).unwrap(),
+        state_t: hex::encode(frontier.root().unwrap()),
+        block_frontier: frontier,
+        parent_proof: vec![],
+        blocks,
+        state: StateCommitment::default(),
+        witnesses,
+    }
+}
+
+#[test]
+fn golden_header_hash() {
+    assert_eq!(header().block_hash().unwrap(), HEADER_HASH);
+}
+
+#[test]
+fn header_hash_ignores_the_hex_spelling() {
+    let mut header = header();
+    header.parent_hash = format!("0x{}", header.parent_hash.to_uppercase());
+    header.tx_root = format!("0x{}", header.tx_root);
+    header.sequencer = SEQUENCER.trim_start_matches("0x").to_string();
+    assert_eq!(header.block_hash().unwrap(), HEADER_HASH);
+}
+
+#[test]
+fn every_header_field_is_hashed() {
+    let changes: [fn(&mut BlockHeader); 7] = [
+        |header| header.height += 1,
+        |header| header.parent_hash = hash("other parent"),
+        |header| header.timestamp += 1,
+        |header| header.tx_root = hash("other tx root"),
+        |header| header.pre_utxo_root = hash("other pre utxo root"),
+        |header| header.post_utxo_root = hash("other post utxo root"),
+        |header| header.sequencer = "0x00000000000000000000000000000000000000a2".to_string(),
+    ];
+    for change in changes {
+        let mut header = header();
+        change(&mut header);
+        assert_ne!(header.block_hash().unwrap(), HEADER_HASH);
+    }
+}
+
+#[test]
+fn rejects_invalid_headers() {
+    let mut invalid = header();
+    invalid.parent_hash = "parent".to_string();
+    assert_eq!(
+        invalid.block_hash(),
+        Err(BlockError::InvalidHash("parent".to_string()))
+    );
+
+    let mut invalid = header();
+    invalid.sequencer = "0xa1".to_string();
+    assert_eq!(
+        invalid.block_hash(),
+        Err(BlockError::InvalidSequencer("0xa1".to_string()))
+    );
+}
+
+#[test]
+fn tx_root_commits_to_the_transactions() {
+    let transactions = vec![deposit_tx("alice", 10), deposit_tx("bob", 20)];
+    let block = block(1, EMPTY_ROOT, transactions.clone());
+    assert!(block.verify_tx_root().is_ok());
+    assert_eq!(transactions_root(&[]).unwrap(), EMPTY_ROOT);
+    assert_eq!(transactions_root(&labelled(3)).unwrap(), TX_ROOT);
+
+    // a missing, a replaced, an extra or a reordered transaction does not match the root
+    let others = [
+        vec![transactions[1].clone(), transactions[0].clone()],
+        vec![transactions[0].clone()],
+        vec![transactions[0].clone(), deposit_tx("carol", 20)],
+        vec![],
+        vec![
+            transactions[0].clone(),
+            transactions[1].clone(),
+            deposit_tx("carol", 30),
+        ],
+    ];
+    for transactions in others {
+        let mut tampered = block.clone();
+        tampered.transactions = transactions;
+        assert!(matches!(
+            tampered.verify_tx_root(),
+            Err(BlockError::TxRootMismatch(header, _)) if header == block.header.tx_root
+        ));
+    }
+}
+
+#[test]
+fn chain_from_the_empty_frontier() {
+    let (blocks, _) = chain(vec![vec![deposit_tx("alice", 10)], vec![], vec![]]);
+    let hashes = verify_chain(&MerkleAccumulator::new(), &[], &blocks).unwrap();
+    let expected: Vec<String> = blocks.iter().map(|block| block.hash().unwrap()).collect();
+    assert_eq!(hashes, expected);
+
+    // the first block of the chain has no parent
+    let mut first = blocks[..1].to_vec();
+    first[0].header.parent_hash = hash("parent");
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &first),
+        Err(TransitionError::BrokenChain(1, parent)) if parent == hash("parent")
+    ));
+}
+
+#[test]
+fn rejects_a_broken_parent_hash() {
+    let (mut blocks, _) = chain(vec![vec![], vec![], vec![]]);
+    // the parent of the third block is the first one
+    blocks[2].header.parent_hash = blocks[0].hash().unwrap();
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &blocks),
+        Err(TransitionError::BrokenChain(3, parent)) if parent == blocks[2].header.parent_hash
+    ));
+
+    // changing a block breaks the link of the next one
+    let (mut blocks, _) = chain(vec![vec![], vec![]]);
+    blocks[0].header.timestamp += 1;
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &blocks),
+        Err(TransitionError::BrokenChain(2, _))
+    ));
+}
+
+#[test]
+fn chain_extends_the_frontier() {
+    let (blocks, _) = chain(vec![vec![], vec![], vec![], vec![]]);
+    let hashes: Vec<Hash> = blocks
+        .iter()
+        .map(|block| parse_hash(&block.hash().unwrap()).unwrap())
+        .collect();
+    let mut frontier = MerkleAccumulator::new();
+    for hash in &hashes[..2] {
+        frontier.append(*hash).unwrap();
+    }
+    let parent_proof = MerkleAccumulator::last_leaf_proof(&hashes[..2]);
+    assert_eq!(parent_proof.len(), 1);
+    assert!(verify_chain(&frontier, &parent_proof, &blocks[2..]).is_ok());
+
+    // the parent must be the last block of the frontier, proven by the proof
+    assert!(matches!(
+        verify_chain(&frontier, &[hashes[1]], &blocks[2..]),
+        Err(TransitionError::BrokenChain(3, _))
+    ));
+    let mut orphan = blocks[2..].to_vec();
+    orphan[0].header.parent_hash = blocks[0].hash().unwrap();
+    assert!(matches!(
+        verify_chain(&frontier, &parent_proof, &orphan),
+        Err(TransitionError::BrokenChain(3, _))
+    ));
+}
+
+#[test]
+fn rejects_an_unexpected_height() {
+    let (blocks, _) = chain(vec![vec![], vec![], vec![]]);
+
+    // a batch cannot start before or after the end of the frontier
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &blocks[1..]),
+        Err(TransitionError::UnexpectedHeight(1, 2))
+    ));
+
+    // nor skip or repeat a height
+    let mut skipped = blocks.clone();
+    skipped[2].header.height = 4;
+    skipped[2].header.parent_hash = blocks[1].hash().unwrap();
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &skipped),
+        Err(TransitionError::UnexpectedHeight(3, 4))
+    ));
+    let mut repeated = blocks.clone();
+    repeated[1].header.height = 1;
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &repeated),
+        Err(TransitionError::UnexpectedHeight(2, 1))
+    ));
+}
+
+#[test]
+fn rejects_a_tx_root_mismatch() {
+    let (mut blocks, _) = chain(vec![vec![deposit_tx("alice", 10)]]);
+    blocks[0].transactions.push(deposit_tx("bob", 20));
+    assert!(matches!(
+        verify_chain(&MerkleAccumulator::new(), &[], &blocks),
+        Err(TransitionError::InvalidBlock(
+            1,
+            BlockError::TxRootMismatch(..)
+        ))
+    ));
+}
+
+#[test]
+fn executes_the_batch() {
+    let (blocks, witnesses) = chain(vec![
+        vec![deposit_tx("alice", 10)],
+        vec![deposit_tx("bob", 20)],
+    ]);
+    // the headers hold the UTXO roots of the host state
+    assert_ne!(blocks[1].header.post_utxo_root, EMPTY_ROOT);
+    let input = inputs(blocks, witnesses);
+    let journal = execute(&input).unwrap();
+    assert_eq!((journal.fromBlock, journal.toBlock), (1, 2));
+    // the batch extends the blocks the contract knows, up to its own blocks
+    assert_eq!(hex::encode(journal.prevBatchHash), input.state_t);
+    assert_eq!(hex::encode(journal.batchHash), input.state_t_1);
+    assert_eq!(
+        journal.prevRoot.0,
+        StateCommitment::default().root().unwrap()
+    );
+    assert_ne!(journal.newRoot, journal.prevRoot);
+}
+
+#[test]
+fn rejects_a_utxo_root_mismatch() {
+    let (blocks, witnesses) = chain(vec![vec![deposit_tx("alice", 10)]]);
+    let computed = blocks[0].header.post_utxo_root.clone();
+
+    // the header claims the deposit did not change the set
+    let mut stale = blocks.clone();
+    stale[0].header.post_utxo_root = EMPTY_ROOT.to_string();
+    assert!(matches!(
+        execute(&inputs(stale, witnesses.clone())),
+        Err(TransitionError::UTXORootMismatch(1, header, root))
+            if header == EMPTY_ROOT && root == computed
+    ));
+
+    // the header claims another set before the block
+    let mut other = blocks.clone();
+    other[0].header.pre_utxo_root = computed.clone();
+    assert!(matches!(
+        execute(&inputs(other, witnesses)),
+        Err(TransitionError::UTXORootMismatch(1, header, root))
+            if header == computed && root == EMPTY_ROOT
+    ));
+}
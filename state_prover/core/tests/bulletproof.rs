@@ -0,0 +1,151 @@
+mod common;
+
+use common::bulletproof::{prove, prove_bits};
+use k256::Scalar;
+use state_prover_core::{
+    commit, hash_to_scalar, point_to_hex, scalar_to_hex, verify_batch, Generators, LigthRangeProof,
+    RangeProofError,
+};
+
+fn blinding(label: &str) -> Scalar {
+    hash_to_scalar(label.as_bytes())
+}
+
+/// Proofs of 42, 0 and the largest amount, with their commitments.
+fn proofs(generators: &Generators) -> Vec<(LigthRangeProof, String)> {
+    [(42, "a"), (0, "b"), (u64::MAX, "c")]
+        .into_iter()
+        .map(|(value, seed)| {
+            let proof = prove(generators, value, &blinding(seed), seed);
+            let commitment = point_to_hex(&commit(value, &blinding(seed)));
+            (proof, commitment)
+        })
+        .collect()
+}
+
+fn batch(proofs: &[(LigthRangeProof, String)]) -> Vec<(&LigthRangeProof, &str)> {
+    proofs
+        .iter()
+        .map(|(proof, commitment)| (proof, commitment.as_str()))
+        .collect()
+}
+
+#[test]
+fn verifies_valid_proofs() {
+    let generators = Generators::new();
+    let proofs = proofs(&generators);
+    for (proof, commitment) in &proofs {
+        assert_eq!(proof.verify(&generators, commitment), Ok(()));
+    }
+    assert_eq!(verify_batch(&generators, &batch(&proofs)), Ok(()));
+    assert_eq!(verify_batch(&generators, &[]), Ok(()));
+}
+
+#[test]
+fn rejects_flipped_scalar() {
+    let generators = Generators::new();
+    let (proof, commitment) = &proofs(&generators)[0];
+    let flip = |value: &str| {
+        let scalar = state_prover_core::scalar_from_hex(value).unwrap();
+        scalar_to_hex(&(scalar + Scalar::ONE))
+    };
+    let fields: [fn(&mut LigthRangeProof) -> &mut String; 5] = [
+        |proof| &mut proof.tx,
+        |proof| &mut proof.txbf,
+        |proof| &mut proof.e,
+        |proof| &mut proof.a0,
+        |proof| &mut proof.b0,
+    ];
+    for field in fields {
+        let mut tampered = proof.clone();
+        let value = flip(field(&mut tampered));
+        *field(&mut tampered) = value;
+        assert_eq!(
+            tampered.verify(&generators, commitment),
+            Err(RangeProofError::InvalidProof(0))
+        );
+    }
+}
+
+#[test]
+fn rejects_swapped_l_r() {
+    let generators = Generators::new();
+    let (proof, commitment) = &proofs(&generators)[0];
+    for round in 0..proof.ind.len() {
+        let mut tampered = proof.clone();
+        let pair = &mut tampered.ind[round];
+        std::mem::swap(&mut pair.L, &mut pair.R);
+        assert_eq!(
+            tampered.verify(&generators, commitment),
+            Err(RangeProofError::InvalidProof(0))
+        );
+    }
+
+    // the rounds are ordered too
+    let mut tampered = proof.clone();
+    tampered.ind.swap(0, 1);
+    assert_eq!(
+        tampered.verify(&generators, commitment),
+        Err(RangeProofError::InvalidProof(0))
+    );
+}
+
+#[test]
+fn rejects_out_of_range_value() {
+    let generators = Generators::new();
+    let gamma = blinding("out of range");
+    // 2^64, and -1 which wraps around the group order: no bit vector of 64 bits matches them
+    let two_64 = Scalar::from(u64::MAX) + Scalar::ONE;
+    for (value, bits) in [(two_64, 0), (two_64, u64::MAX), (-Scalar::ONE, u64::MAX)] {
+        let proof = prove_bits(&generators, &value, bits, &gamma, "out of range");
+        let commitment = proof.V.clone();
+        assert_eq!(
+            proof.verify(&generators, &commitment),
+            Err(RangeProofError::InvalidProof(0))
+        );
+    }
+}
+
+#[test]
+fn rejects_wrong_commitment_and_rounds() {
+    let generators = Generators::new();
+    let proofs = proofs(&generators);
+    let (proof, _) = &proofs[0];
+    assert_eq!(
+        proof.verify(&generators, &proofs[1].1),
+        Err(RangeProofError::CommitmentMismatch(0))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.ind.pop();
+    assert_eq!(
+        tampered.verify(&generators, &proofs[0].1),
+        Err(RangeProofError::WrongRounds(0, 5))
+    );
+
+    let mut tampered = proof.clone();
+    tampered.T1 = "02aa".to_string();
+    assert_eq!(
+        tampered.verify(&generators, &proofs[0].1),
+        Err(RangeProofError::InvalidEncoding(0, "T1"))
+    );
+}
+
+#[test]
+fn reports_the_bad_proof_of_a_batch() {
+    let generators = Generators::new();
+    let mut proofs = proofs(&generators);
+    let a0 = proofs[0].0.a0.clone();
+    proofs[1].0.a0 = a0;
+    assert_eq!(
+        verify_batch(&generators, &batch(&proofs)),
+        Err(RangeProofError::InvalidProof(1))
+    );
+
+    // a valid proof moved to another position keeps its index in the error
+    proofs.swap(1, 2);
+    assert_eq!(
+        verify_batch(&generators, &batch(&proofs)),
+        Err(RangeProofError::InvalidProof(2))
+    );
+}
@@ -0,0 +1,160 @@
+//! Test prover of the range proofs, the transcript of `state_prover_core::bulletproof` with
+//! nonces derived from a seed so that the tests are deterministic.
+use k256::{ProjectivePoint, Scalar};
+use state_prover_core::{
+    hash_to_scalar, point_to_bytes, point_to_hex, scalar_to_hex, Generators, LeftRigth,
+    LigthRangeProof, RANGE_BITS,
+};
+
+fn challenge(parts: &[&[u8]]) -> Scalar {
+    hash_to_scalar(&parts.concat())
+}
+
+fn inner_product(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    a.iter()
+        .zip(b)
+        .fold(Scalar::ZERO, |sum, (a, b)| sum + a * b)
+}
+
+fn multi_mul(scalars: &[Scalar], points: &[ProjectivePoint]) -> ProjectivePoint {
+    scalars
+        .iter()
+        .zip(points)
+        .fold(ProjectivePoint::IDENTITY, |sum, (s, p)| sum + *p * s)
+}
+
+/// Proof that `commitment = gamma * G + value * H` opens to `value`, with the range proof of
+/// its 64 bits.
+pub fn prove(generators: &Generators, value: u64, gamma: &Scalar, seed: &str) -> LigthRangeProof {
+    prove_bits(generators, &Scalar::from(value), value, gamma, seed)
+}
+
+/// Same as `prove`, but the bit vector is the one of `bits` while the commitment is to `value`:
+/// an honest prover has `value == bits`, another value is out of the range of the proof.
+pub fn prove_bits(
+    generators: &Generators,
+    value: &Scalar,
+    bits: u64,
+    gamma: &Scalar,
+    seed: &str,
+) -> LigthRangeProof {
+    let n = RANGE_BITS;
+    let nonce = |label: &str| hash_to_scalar(format!("{} {}", seed, label).as_bytes());
+    let nonces = |label: &str| -> Vec<Scalar> {
+        (0..n).map(|i| nonce(&format!("{} {}", label, i))).collect()
+    };
+    let g = ProjectivePoint::GENERATOR;
+
+    let v = g * gamma + generators.value * value;
+    let a_l: Vec<Scalar> = (0..n).map(|i| Scalar::from((bits >> i) & 1)).collect();
+    let a_r: Vec<Scalar> = a_l.iter().map(|a| *a - Scalar::ONE).collect();
+    let (alpha, rho) = (nonce("alpha"), nonce("rho"));
+    let (s_l, s_r) = (nonces("s_l"), nonces("s_r"));
+    let a = g * alpha + multi_mul(&a_l, &generators.g) + multi_mul(&a_r, &generators.h);
+    let s = g * rho + multi_mul(&s_l, &generators.g) + multi_mul(&s_r, &generators.h);
+
+    let (v_bytes, a_bytes, s_bytes) = (point_to_bytes(&v), point_to_bytes(&a), point_to_bytes(&s));
+    let y = challenge(&[&v_bytes, &a_bytes, &s_bytes]);
+    let z = challenge(&[&v_bytes, &a_bytes, &s_bytes, &y.to_bytes()]);
+    let z2 = z * z;
+    let mut y_powers = Vec::with_capacity(n);
+    let mut two_powers = Vec::with_capacity(n);
+    let (mut y_power, mut two_power) = (Scalar::ONE, Scalar::ONE);
+    for _ in 0..n {
+        y_powers.push(y_power);
+        two_powers.push(two_power);
+        y_power *= y;
+        two_power *= Scalar::from(2u64);
+    }
+
+    let l0: Vec<Scalar> = a_l.iter().map(|a| *a - z).collect();
+    let r0: Vec<Scalar> = (0..n)
+        .map(|i| y_powers[i] * (a_r[i] + z) + z2 * two_powers[i])
+        .collect();
+    let r1: Vec<Scalar> = (0..n).map(|i| y_powers[i] * s_r[i]).collect();
+    let t1 = inner_product(&l0, &r1) + inner_product(&s_l, &r0);
+    let t2 = inner_product(&s_l, &r1);
+    let (tau1, tau2) = (nonce("tau1"), nonce("tau2"));
+    let t1_point = generators.value * t1 + g * tau1;
+    let t2_point = generators.value * t2 + g * tau2;
+    let x = challenge(&[
+        &y.to_bytes(),
+        &z.to_bytes(),
+        &point_to_bytes(&t1_point),
+        &point_to_bytes(&t2_point),
+    ]);
+
+    let mut l: Vec<Scalar> = (0..n).map(|i| l0[i] + s_l[i] * x).collect();
+    let mut r: Vec<Scalar> = (0..n).map(|i| r0[i] + r1[i] * x).collect();
+    let t_hat = inner_product(&l, &r);
+    let tau_x = tau2 * x * x + tau1 * x + z2 * gamma;
+    let mu = alpha + rho * x;
+    let x_ip = challenge(&[
+        &x.to_bytes(),
+        &t_hat.to_bytes(),
+        &tau_x.to_bytes(),
+        &mu.to_bytes(),
+    ]);
+
+    // inner product argument on (G_i, y^-i * H_i) with U' = x_ip * U
+    let u_point = generators.u * x_ip;
+    let y_inverse = y.invert().unwrap();
+    let mut g_vector = generators.g.clone();
+    let mut h_vector = Vec::with_capacity(n);
+    let mut y_inverse_power = Scalar::ONE;
+    for h in &generators.h {
+        h_vector.push(*h * y_inverse_power);
+        y_inverse_power *= y_inverse;
+    }
+    let mut rounds = Vec::new();
+    let mut previous = x_ip;
+    while l.len() > 1 {
+        let half = l.len() / 2;
+        let (a_lo, a_hi) = l.split_at(half);
+        let (b_lo, b_hi) = r.split_at(half);
+        let (g_lo, g_hi) = g_vector.split_at(half);
+        let (h_lo, h_hi) = h_vector.split_at(half);
+        let l_point =
+            multi_mul(a_lo, g_hi) + multi_mul(b_hi, h_lo) + u_point * inner_product(a_lo, b_hi);
+        let r_point =
+            multi_mul(a_hi, g_lo) + multi_mul(b_lo, h_hi) + u_point * inner_product(a_hi, b_lo);
+        let u = challenge(&[
+            &previous.to_bytes(),
+            &point_to_bytes(&l_point),
+            &point_to_bytes(&r_point),
+        ]);
+        previous = u;
+        let u_inverse = u.invert().unwrap();
+        let next_l = (0..half)
+            .map(|i| a_lo[i] * u + a_hi[i] * u_inverse)
+            .collect();
+        let next_r = (0..half)
+            .map(|i| b_lo[i] * u_inverse + b_hi[i] * u)
+            .collect();
+        let next_g = (0..half)
+            .map(|i| g_lo[i] * u_inverse + g_hi[i] * u)
+            .collect();
+        let next_h = (0..half)
+            .map(|i| h_lo[i] * u + h_hi[i] * u_inverse)
+            .collect();
+        (l, r, g_vector, h_vector) = (next_l, next_r, next_g, next_h);
+        rounds.push(LeftRigth {
+            L: point_to_hex(&l_point),
+            R: point_to_hex(&r_point),
+        });
+    }
+
+    LigthRangeProof {
+        V: point_to_hex(&v),
+        A: point_to_hex(&a),
+        S: point_to_hex(&s),
+        T1: point_to_hex(&t1_point),
+        T2: point_to_hex(&t2_point),
+        tx: scalar_to_hex(&t_hat),
+        txbf: scalar_to_hex(&tau_x),
+        e: scalar_to_hex(&mu),
+        a0: scalar_to_hex(&l[0]),
+        b0: scalar_to_hex(&r[0]),
+        ind: rounds,
+    }
+}
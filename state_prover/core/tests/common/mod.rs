@@ -0,0 +1,209 @@
+//! Fixtures shared by the integration tests.
+//!
+//! Every point and scalar is derived from a label with `hash_to_scalar`, tests/vectors/
+//! gen_vectors.py derives the same values to compute the golden vectors.
+#![allow(dead_code)]
+
+pub mod bulletproof;
+pub mod ringct;
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::ProjectivePoint;
+use state_prover_core::transition::{apply_transactions, TransitionError};
+use state_prover_core::{
+    commit_clear_amount, hash_to_scalar, keccak256, point_from_hex, point_to_hex, scalar_to_hex,
+    CoinbaseUTXO, ExitUTXO, Generators, LeftRigth, LigthRangeProof, PaymentUTXO,
+    PendingTransaction, PendingUserDepositTx, State, StateCommitment, UserDepositEvent,
+    AMOUNT_UNIT_WEI, UTXO,
+};
+
+pub fn scalar(label: &str) -> String {
+    scalar_to_hex(&hash_to_scalar(label.as_bytes()))
+}
+
+/// `hash_to_scalar(label) * G`, compressed.
+pub fn point(label: &str) -> String {
+    point_to_hex(&(ProjectivePoint::GENERATOR * hash_to_scalar(label.as_bytes())))
+}
+
+/// Uncompressed SEC1 encoding of a point given in hex.
+pub fn uncompressed(point: &str) -> String {
+    let point = point_from_hex(point).unwrap();
+    hex::encode(point.to_affine().to_encoded_point(false).as_bytes())
+}
+
+pub fn tx_hash() -> String {
+    hex::encode(keccak256(b"transaction"))
+}
+
+pub fn range_proof() -> LigthRangeProof {
+    LigthRangeProof {
+        V: point("V"),
+        A: point("A"),
+        S: point("S"),
+        T1: point("T1"),
+        T2: point("T2"),
+        tx: scalar("tx"),
+        txbf: scalar("txbf"),
+        e: scalar("e"),
+        a0: scalar("a0"),
+        b0: scalar("b0"),
+        ind: (0..6)
+            .map(|i| LeftRigth {
+                L: point(&format!("L{}", i)),
+                R: point(&format!("R{}", i)),
+            })
+            .collect(),
+    }
+}
+
+pub fn payment() -> PaymentUTXO {
+    let mut amount = keccak256(b"amount").to_vec();
+    amount.extend(keccak256(b"blinding factor"));
+    PaymentUTXO {
+        version: "0x01".to_string(),
+        transaction_hash: tx_hash(),
+        output_index: 3,
+        public_key: point("payment key"),
+        unlock_time: Some(7),
+        amount: hex::encode(amount),
+        currency: "ETH".to_string(),
+        commitment: point("V"),
+        rangeProof: range_proof(),
+        rG: point("payment rG"),
+        hash: String::new(),
+    }
+    .hashed()
+    .unwrap()
+}
+
+pub fn exit() -> ExitUTXO {
+    ExitUTXO {
+        transaction_hash: tx_hash(),
+        output_index: 1,
+        public_key: point("exit key"),
+        unlock_time: None,
+        amount: "5".to_string(),
+        currency: "ETH".to_string(),
+        commitment: commit_clear_amount("5").unwrap(),
+        exitChain: "sepolia".to_string(),
+        hash: String::new(),
+    }
+    .hashed()
+    .unwrap()
+}
+
+pub fn coinbase() -> CoinbaseUTXO {
+    CoinbaseUTXO {
+        version: "0x01".to_string(),
+        transaction_hash: "00".repeat(32),
+        output_index: 0,
+        public_key: point("coinbase key"),
+        unlock_time: None,
+        amount: "10".to_string(),
+        currency: "ETH".to_string(),
+        commitment: commit_clear_amount("10").unwrap(),
+        rG: point("coinbase rG"),
+        hash: String::new(),
+    }
+    .hashed()
+    .unwrap()
+}
+
+/// One UTXO of every variant, in tag order.
+pub fn utxos() -> [UTXO; 3] {
+    [
+        UTXO::Payment(Box::new(payment())),
+        UTXO::Exit(exit()),
+        UTXO::Coinbase(coinbase()),
+    ]
+}
+
+/// Coinbase output of a deposit of `amount`, owned by the key of `label`.
+pub fn deposit(label: &str, amount: u64) -> UTXO {
+    UTXO::Coinbase(
+        CoinbaseUTXO {
+            version: "0x01".to_string(),
+            transaction_hash: "00".repeat(32),
+            output_index: 0,
+            public_key: point(label),
+            unlock_time: None,
+            amount: amount.to_string(),
+            currency: "ETH".to_string(),
+            commitment: commit_clear_amount(&amount.to_string()).unwrap(),
+            rG: point(&format!("{} rG", label)),
+            hash: String::new(),
+        }
+        .hashed()
+        .unwrap(),
+    )
+}
+
+/// Deposit of `amount` units to the key of `label`.
+pub fn deposit_tx(label: &str, amount: u64) -> PendingTransaction {
+    let event = UserDepositEvent {
+        txId: hex::encode(keccak256(label.as_bytes())),
+        amount: (amount * AMOUNT_UNIT_WEI).to_string(),
+        currency: "ETH".to_string(),
+        root_block_number: 1,
+        root_blockchain: "sepolia".to_string(),
+        public_key: point(label),
+        r_g: point(&format!("{} rG", label)),
+    };
+    PendingTransaction::PendingDeposit(
+        PendingUserDepositTx::from_user_deposit_event(event).unwrap(),
+    )
+}
+
+/// `tx`, a deposit, made by the root chain transaction `tx_id` instead.
+pub fn with_tx_id(tx: &PendingTransaction, tx_id: &str) -> PendingTransaction {
+    let PendingTransaction::PendingDeposit(deposit) = tx else {
+        panic!("not a deposit");
+    };
+    let mut deposit = PendingUserDepositTx {
+        txId: tx_id.to_string(),
+        ..deposit.clone()
+    };
+    deposit.hash = deposit.tx_hash().unwrap();
+    PendingTransaction::PendingDeposit(deposit)
+}
+
+/// Exit output of `amount` to the key of `label`.
+pub fn withdrawal(label: &str, amount: u64) -> UTXO {
+    UTXO::Exit(
+        ExitUTXO {
+            transaction_hash: tx_hash(),
+            output_index: 0,
+            public_key: point(label),
+            unlock_time: None,
+            amount: amount.to_string(),
+            currency: "ETH".to_string(),
+            commitment: commit_clear_amount(&amount.to_string()).unwrap(),
+            exitChain: "sepolia".to_string(),
+            hash: String::new(),
+        }
+        .hashed()
+        .unwrap(),
+    )
+}
+
+/// State holding `utxos`, as a genesis state.
+pub fn genesis(utxos: &[UTXO]) -> State {
+    let mut state = State::new();
+    for utxo in utxos {
+        assert!(state.insert_utxo(utxo.clone()).unwrap());
+    }
+    state
+}
+
+/// Applies a block to the host state and checks its witness as the guest does.
+pub fn apply(
+    state: &mut State,
+    transactions: &[PendingTransaction],
+) -> Result<StateCommitment, TransitionError> {
+    let commitment = state.commitment();
+    let witness = state.apply_block(transactions)?;
+    let applied = apply_transactions(&Generators::new(), &commitment, transactions, &witness)?;
+    assert_eq!(applied, state.commitment());
+    Ok(applied)
+}
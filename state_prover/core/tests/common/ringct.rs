@@ -0,0 +1,132 @@
+//! Test signer of the ring signatures, its nonces are derived from the message so that the
+//! tests are deterministic.
+use k256::{ProjectivePoint, Scalar};
+use state_prover_core::{
+    hash_to_point, hash_to_scalar, keccak256, point_from_hex, point_to_bytes, point_to_hex,
+    scalar_to_hex, PendingRingCT, Point, MLSAG, UTXO,
+};
+
+fn nonce(message: &str, label: &str, row: usize, column: usize) -> Scalar {
+    hash_to_scalar(format!("{} {} {} {}", message, label, row, column).as_bytes())
+}
+
+/// Signs `message` over `ring`, given row by row, knowing the `secrets` of row `index`. The
+/// first `linkable` columns get a key image.
+pub fn sign(
+    message: &str,
+    ring: &[Vec<ProjectivePoint>],
+    index: usize,
+    secrets: &[Scalar],
+    linkable: usize,
+) -> MLSAG {
+    let rows = ring.len();
+    let columns = secrets.len();
+    let transcript_start = keccak256(message.as_bytes()).to_vec();
+    let key_images: Vec<ProjectivePoint> = (0..linkable)
+        .map(|column| hash_to_point(&point_to_bytes(&ring[index][column])) * secrets[column])
+        .collect();
+    let alphas: Vec<Scalar> = (0..columns)
+        .map(|column| nonce(message, "alpha", index, column))
+        .collect();
+
+    let mut responses = vec![vec![Scalar::ZERO; columns]; rows];
+    let mut challenges = vec![Scalar::ZERO; rows];
+    let mut transcript = transcript_start.clone();
+    for column in 0..columns {
+        transcript.extend(point_to_bytes(
+            &(ProjectivePoint::GENERATOR * alphas[column]),
+        ));
+        if column < linkable {
+            let base = hash_to_point(&point_to_bytes(&ring[index][column]));
+            transcript.extend(point_to_bytes(&(base * alphas[column])));
+        }
+    }
+    let mut c = hash_to_scalar(&transcript);
+    let mut row = (index + 1) % rows;
+    while row != index {
+        challenges[row] = c;
+        let mut transcript = transcript_start.clone();
+        for column in 0..columns {
+            let response = nonce(message, "response", row, column);
+            responses[row][column] = response;
+            let point = ring[row][column];
+            transcript.extend(point_to_bytes(
+                &(ProjectivePoint::GENERATOR * response + point * c),
+            ));
+            if column < linkable {
+                let base = hash_to_point(&point_to_bytes(&point));
+                transcript.extend(point_to_bytes(&(base * response + key_images[column] * c)));
+            }
+        }
+        c = hash_to_scalar(&transcript);
+        row = (row + 1) % rows;
+    }
+    challenges[index] = c;
+    for column in 0..columns {
+        responses[index][column] = alphas[column] - c * secrets[column];
+    }
+
+    MLSAG {
+        message: message.to_string(),
+        ring: ring.iter().flatten().map(Point::from_point).collect(),
+        c: scalar_to_hex(&challenges[0]),
+        responses: responses
+            .iter()
+            .map(|row| row.iter().map(scalar_to_hex).collect())
+            .collect(),
+        key_images: key_images.iter().map(point_to_hex).collect(),
+    }
+}
+
+/// Ring of a RingCT transaction: the public key of each member, then its commitment minus the
+/// pseudo commitment.
+pub fn ring_ct_ring(
+    members: &[UTXO],
+    pseudo_commitment: &ProjectivePoint,
+) -> Vec<Vec<ProjectivePoint>> {
+    members
+        .iter()
+        .map(|member| {
+            vec![
+                point_from_hex(&member.get_public_key()).unwrap(),
+                point_from_hex(&member.get_commitment()).unwrap() - pseudo_commitment,
+            ]
+        })
+        .collect()
+}
+
+/// RingCT transaction spending row `index` of `members`, whose secret key is `secret_key` and
+/// whose commitment minus `pseudo_commitment` is `z * G`.
+pub fn ring_ct(
+    members: &[UTXO],
+    index: usize,
+    secret_key: &Scalar,
+    z: &Scalar,
+    pseudo_commitment: &ProjectivePoint,
+    outputs: Vec<UTXO>,
+    fee: u64,
+) -> PendingRingCT {
+    let mut tx = PendingRingCT {
+        inputs: members.iter().map(UTXO::get_hash).collect(),
+        outputs,
+        hash: String::new(),
+        signature: String::new(),
+        pseudo_commitments: vec![point_to_hex(pseudo_commitment)],
+        fee,
+    };
+    tx.hash = tx.tx_hash().unwrap();
+    let ring = ring_ct_ring(members, pseudo_commitment);
+    let signature = sign(&tx.hash, &ring, index, &[*secret_key, *z], 1);
+    tx.signature = hex::encode(signature.to_bytes().unwrap());
+    tx
+}
+
+/// Replaces the signature of `tx`.
+pub fn set_signature(tx: &mut PendingRingCT, signature: &MLSAG) {
+    tx.signature = hex::encode(signature.to_bytes().unwrap());
+}
+
+/// Signature carried by `tx`.
+pub fn signature(tx: &PendingRingCT) -> MLSAG {
+    MLSAG::from_bytes(&hex::decode(&tx.signature).unwrap()).unwrap()
+}
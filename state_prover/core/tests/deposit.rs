@@ -0,0 +1,142 @@
+mod common;
+
+use common::{apply, coinbase, deposit_tx, genesis, utxos, with_tx_id};
+use state_prover_core::transition::{apply_transactions, TransitionError};
+use state_prover_core::{
+    commit_clear_amount, deposit_hash, CoinbaseUTXO, CommitmentError, Deposit, DepositError,
+    Generators, PendingTransaction, PendingUserDepositTx, UTXO,
+};
+
+// `deposit_hash` of the deposits of Alice and Bob, computed by the port of
+// tests/vectors/gen_vectors.py.
+const DEPOSIT_HASH: &str = "f44120de493473755be27b42779c13d608e57ab3d7ee1fea22ac650533bdb914";
+
+fn deposit(tx: &PendingTransaction) -> &PendingUserDepositTx {
+    match tx {
+        PendingTransaction::PendingDeposit(deposit) => deposit,
+        PendingTransaction::PendingRingCTx(_) => panic!("not a deposit"),
+    }
+}
+
+/// `tx`, a deposit, minting `output` instead.
+fn with_output(tx: &PendingTransaction, output: UTXO) -> PendingTransaction {
+    let mut deposit = PendingUserDepositTx {
+        output,
+        ..deposit(tx).clone()
+    };
+    deposit.hash = deposit.tx_hash().unwrap();
+    PendingTransaction::PendingDeposit(deposit)
+}
+
+#[test]
+fn golden_deposit_hash() {
+    let deposits: Vec<Deposit> = [deposit_tx("alice", 10), deposit_tx("bob", 20)]
+        .iter()
+        .map(|tx| Deposit::from_deposit_tx(deposit(tx)).unwrap())
+        .collect();
+    assert_eq!(hex::encode(deposit_hash(&deposits)), DEPOSIT_HASH);
+    assert_eq!(deposit_hash(&[]), [0u8; 32]);
+
+    // the deposits are committed in order
+    let reversed: Vec<Deposit> = deposits.into_iter().rev().collect();
+    assert_ne!(hex::encode(deposit_hash(&reversed)), DEPOSIT_HASH);
+}
+
+#[test]
+fn deposit_output_must_be_a_coinbase() {
+    let alice = deposit_tx("alice", 10);
+    let [payment, exit, _] = utxos();
+    for output in [payment, exit] {
+        let hash = output.get_hash();
+        let tx = with_output(&alice, output);
+        assert_eq!(
+            Deposit::from_deposit_tx(deposit(&tx)),
+            Err(DepositError::InvalidOutput(hash.clone()))
+        );
+        assert!(matches!(
+            apply(&mut genesis(&[]), &[tx]),
+            Err(TransitionError::InvalidDeposit(_, DepositError::InvalidOutput(output)))
+                if output == hash
+        ));
+    }
+    let tx = with_output(&alice, UTXO::Coinbase(coinbase()));
+    assert!(apply(&mut genesis(&[]), &[tx]).is_ok());
+}
+
+#[test]
+fn deposits_are_applied_once() {
+    let alice = deposit_tx("alice", 10);
+    let tx_id = deposit(&alice).txId.clone();
+    // another output, minted by the same root chain transaction
+    let replay = with_tx_id(&deposit_tx("bob", 20), &tx_id);
+
+    // within a block
+    let mut state = genesis(&[]);
+    let before = state.commitment();
+    assert!(matches!(
+        state.apply_block(&[alice.clone(), replay.clone()]),
+        Err(TransitionError::DuplicateDeposit(_, id)) if id == tx_id
+    ));
+    assert_eq!(state.commitment(), before);
+
+    // across blocks
+    apply(&mut state, std::slice::from_ref(&alice)).unwrap();
+    assert!(state.is_deposited(&tx_id));
+    assert!(matches!(
+        state.apply_block(std::slice::from_ref(&replay)),
+        Err(TransitionError::DuplicateDeposit(_, id)) if id == tx_id
+    ));
+
+    // the guest rejects a witness proving it absent from another tree
+    let commitment = state.commitment();
+    let witness = genesis(&[])
+        .apply_block(std::slice::from_ref(&replay))
+        .unwrap();
+    assert!(matches!(
+        apply_transactions(&Generators::new(), &commitment, &[replay], &witness),
+        Err(TransitionError::DuplicateDeposit(_, id)) if id == tx_id
+    ));
+}
+
+#[test]
+fn malformed_deposit_id() {
+    // the hash of the transaction cannot be computed either, the claimed one is kept
+    let tx = PendingTransaction::PendingDeposit(PendingUserDepositTx {
+        txId: "alice".to_string(),
+        ..deposit(&deposit_tx("alice", 10)).clone()
+    });
+    assert!(matches!(
+        genesis(&[]).apply_block(std::slice::from_ref(&tx)),
+        Err(TransitionError::InvalidDeposit(
+            _,
+            DepositError::InvalidEncoding(_)
+        ))
+    ));
+}
+
+#[test]
+fn deposit_commitment_must_open_to_its_amount() {
+    let alice = deposit_tx("alice", 10);
+    let UTXO::Coinbase(output) = &deposit(&alice).output else {
+        panic!("not a coinbase");
+    };
+    let output = CoinbaseUTXO {
+        commitment: commit_clear_amount("11").unwrap(),
+        hash: String::new(),
+        ..output.clone()
+    }
+    .hashed()
+    .unwrap();
+    let tx = with_output(&alice, UTXO::Coinbase(output));
+    assert!(matches!(
+        Deposit::from_deposit_tx(deposit(&tx)),
+        Err(DepositError::InvalidAmount(CommitmentError::Mismatch(..)))
+    ));
+    assert!(matches!(
+        apply(&mut genesis(&[]), &[tx]),
+        Err(TransitionError::InvalidDeposit(
+            _,
+            DepositError::InvalidAmount(CommitmentError::Mismatch(..))
+        ))
+    ));
+}
@@ -0,0 +1,56 @@
+use alloy_primitives::B256;
+use alloy_sol_types::SolType;
+use state_prover_core::{keccak256, StateTransitionJournal};
+
+// ABI encoding of `journal()`, one 32 bytes word per field in declaration order, computed by the
+// port of tests/vectors/gen_vectors.py. It is what the contract reads with `abi.decode`.
+const JOURNAL: [&str; 8] = [
+    "674ea1d57839581c7b8f030733123629342ebe4cbcfabe98c0766d15d3793bb2", // prevRoot
+    "c1c537c4a4ef62d5983a5d4ae549a6dff1dc91c8b2e9b109fa80ebbfec0c2f49", // newRoot
+    "0000000000000000000000000000000000000000000000000000000000000003", // fromBlock
+    "0000000000000000000000000000000000000000000000000000000000000005", // toBlock
+    "293b062708db041060039d215acdf44891a5c2148a70c842795ef78af32034b0", // prevBatchHash
+    "a9126e4bf867072165130f767d49b03b9370774fbfb5224842ac5b288851843a", // batchHash
+    "5f64eae0fc590a819e523a183a9fd4edc3a1c8c4a7b48218351d61312d3f0ea9", // depositHash
+    "8f920a39984cc439587762c50a220d6cc5590b1c4ecb08553287920ec5b8472e", // withdrawalRoot
+];
+
+fn hash(label: &str) -> B256 {
+    B256::from(keccak256(label.as_bytes()))
+}
+
+fn journal() -> StateTransitionJournal {
+    StateTransitionJournal {
+        prevRoot: hash("prev root"),
+        newRoot: hash("new root"),
+        fromBlock: 3,
+        toBlock: 5,
+        prevBatchHash: hash("prev batch"),
+        batchHash: hash("batch"),
+        depositHash: hash("deposits"),
+        withdrawalRoot: hash("withdrawals"),
+    }
+}
+
+#[test]
+fn golden_journal_encoding() {
+    let encoded = StateTransitionJournal::abi_encode(&journal());
+    assert_eq!(hex::encode(&encoded), JOURNAL.concat());
+    // the guest commits the `SolValue` encoding, the host and the publisher decode it as above
+    assert_eq!(alloy_sol_types::SolValue::abi_encode(&journal()), encoded);
+    assert_eq!(
+        StateTransitionJournal::abi_decode(&encoded, true).unwrap(),
+        journal()
+    );
+}
+
+#[test]
+fn rejects_malformed_journals() {
+    let encoded = StateTransitionJournal::abi_encode(&journal());
+    assert!(StateTransitionJournal::abi_decode(&encoded[..encoded.len() - 1], true).is_err());
+
+    // a block number above 64 bits does not fit the field
+    let mut overflow = encoded.clone();
+    overflow[2 * 32] = 1;
+    assert!(StateTransitionJournal::abi_decode(&overflow, true).is_err());
+}
@@ -0,0 +1,121 @@
+use state_prover_core::merkleTree::MerkleTree as HexMerkleTree;
+use state_prover_core::{
+    hex_leaf, keccak256, verify_hex_proof, verify_proof, Hash, Leaf, MerkleTree,
+};
+
+/// Deterministic leaves, so a failing size can be replayed.
+fn leaves(count: usize) -> Vec<Hash> {
+    (0..count as u64)
+        .map(|i| keccak256(&i.to_be_bytes()))
+        .collect()
+}
+
+/// Pseudo-random tree sizes drawn from a keccak chain.
+fn random_sizes(count: usize, max: usize) -> Vec<usize> {
+    let mut seed = keccak256(b"merkle proof sizes");
+    (0..count)
+        .map(|_| {
+            seed = keccak256(&seed);
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&seed[..8]);
+            1 + (u64::from_be_bytes(word) as usize) % max
+        })
+        .collect()
+}
+
+fn assert_round_trip(size: usize) {
+    let tree = MerkleTree::new(leaves(size));
+    let root = tree.root().unwrap();
+    for (index, leaf) in tree.leaves().iter().enumerate() {
+        let proof = tree.generate_proof(index).unwrap();
+        assert!(
+            verify_proof(&root, leaf, &proof),
+            "leaf {} of a tree of {} leaves",
+            index,
+            size
+        );
+    }
+}
+
+#[test]
+fn proofs_verify_for_every_leaf_of_small_trees() {
+    // covers every shape of odd-sized levels up to depth 7
+    for size in 1..=129 {
+        assert_round_trip(size);
+    }
+}
+
+#[test]
+fn proofs_verify_for_random_tree_sizes() {
+    for size in random_sizes(16, 3000) {
+        assert_round_trip(size);
+    }
+}
+
+#[test]
+fn promoted_leaf_skips_the_missing_siblings() {
+    // the fifth leaf is promoted twice before being paired with the root of the first four
+    let tree = MerkleTree::new(leaves(5));
+    let proof = tree.generate_proof(4).unwrap();
+    assert_eq!(proof, vec![tree.levels()[2][0]]);
+    assert!(verify_proof(
+        &tree.root().unwrap(),
+        &tree.leaves()[4],
+        &proof
+    ));
+}
+
+#[test]
+fn single_leaf_tree_has_an_empty_proof() {
+    let tree = MerkleTree::new(leaves(1));
+    let proof = tree.generate_proof(0).unwrap();
+    assert!(proof.is_empty());
+    assert!(verify_proof(
+        &tree.root().unwrap(),
+        &tree.leaves()[0],
+        &proof
+    ));
+    assert!(tree.generate_proof(1).is_none());
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let tree = MerkleTree::new(leaves(11));
+    let root = tree.root().unwrap();
+    for (index, leaf) in tree.leaves().iter().enumerate() {
+        let proof = tree.generate_proof(index).unwrap();
+        let other = tree.leaves()[(index + 1) % 11];
+        assert!(!verify_proof(&root, &other, &proof));
+        assert!(!verify_proof(&keccak256(&root), leaf, &proof));
+        for position in 0..proof.len() {
+            let mut tampered = proof.clone();
+            tampered[position][0] ^= 1;
+            assert!(!verify_proof(&root, leaf, &tampered));
+        }
+        assert!(!verify_proof(&root, leaf, &proof[..proof.len() - 1]));
+    }
+}
+
+#[test]
+fn legacy_hex_proofs_verify() {
+    for size in [1, 2, 3, 6, 7, 13, 32, 33] {
+        let data: Vec<String> = (0..size).map(|i| format!("0x{:04x}", 1000 + i)).collect();
+        let leaves: Vec<Leaf> = data.iter().cloned().map(Leaf::Hex).collect();
+        let tree = HexMerkleTree::new(&leaves).unwrap();
+        let root = tree.root.as_ref().unwrap().data.clone();
+        let outsider = hex::encode(hex_leaf("0xdead").unwrap());
+        for (index, item) in data.iter().enumerate() {
+            let leaf = hex::encode(hex_leaf(item).unwrap());
+            let proof = tree.generate_proof(index);
+            assert!(verify_hex_proof(&root, &leaf, &proof).unwrap());
+            assert!(!verify_hex_proof(&root, &outsider, &proof).unwrap());
+        }
+    }
+}
+
+#[test]
+fn malformed_hex_hashes_are_errors() {
+    let root = hex::encode(keccak256(b"root"));
+    assert!(verify_hex_proof(&root, "0x1234", &[] as &[&str]).is_err());
+    assert!(verify_hex_proof(&root, &root, &["0xzz"]).is_err());
+}
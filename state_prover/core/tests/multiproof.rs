@@ -0,0 +1,212 @@
+use state_prover_core::{
+    keccak256, parse_hash, process_multi_proof, verify_multi_proof, verify_proof, Hash,
+    MerkleError, MerkleTree,
+};
+
+// `getMultiProof` of @openzeppelin/merkle-tree over `leaves(8)`, for the leaves 1, 2 and 5, and
+// over `leaves(5)`, for the leaves 0, 3 and 4, computed by the port of tests/vectors/
+// gen_vectors.py.
+const OZ_ROOT_8: &str = "16257cfad4a3b033c2bf277b630fdeebd2f7796edaffff01282aa19be32c648d";
+const OZ_PROOF_8: [&str; 4] = [
+    "011b4d03dd8c01f1049143cf9c4c817e4b167f1d1b83e5c6f0f10d89ba1e7bce",
+    "d4c69e49e83a6047f46e42b2d053a1f0c6e70ea42862e5ef4ad66b3666c5e2af",
+    "d2ed8d75f801ae8a206c07ff9b104f0e005238dcd1cbaf844fd9f40d63174c56",
+    "37e9108cecf68a87ef8945922adc49a3d5220b1d9a49b69f1bbd69da0316902b",
+];
+const OZ_FLAGS_8: [bool; 6] = [false, false, false, true, false, true];
+const OZ_ROOT_5: &str = "3507565427051dd46be55ec878eece81a28b31568b09e996c1c0738353cc14a5";
+const OZ_PROOF_5: [&str; 2] = [
+    "6c31fc15422ebad28aaf9089c306702f67540b53c7eea8b7d2941044b027100f",
+    "859f11b75569a4eb0496c5138fd42cc52aee8cf5c4e7cfafe58c92b2ed138e04",
+];
+const OZ_FLAGS_5: [bool; 4] = [false, false, true, true];
+
+/// Deterministic leaves, the same as tests/vectors/gen_vectors.py.
+fn leaves(count: usize) -> Vec<Hash> {
+    (0..count as u64)
+        .map(|i| keccak256(&i.to_be_bytes()))
+        .collect()
+}
+
+fn hashes(hex: &[&str]) -> Vec<Hash> {
+    hex.iter().map(|hash| parse_hash(hash).unwrap()).collect()
+}
+
+/// Every subset of the leaves of a tree of `size` leaves, as long as `size` is small.
+fn subsets(size: usize) -> impl Iterator<Item = Vec<usize>> {
+    (0u32..1 << size).map(move |mask| (0..size).filter(|i| mask >> i & 1 == 1).collect())
+}
+
+#[test]
+fn matches_openzeppelin_get_multi_proof() {
+    // OpenZeppelin stores the leaves in reverse order, a complete tree of 8 leaves has the same
+    // shape in both layouts
+    let oz_leaves = leaves(8);
+    let reversed: Vec<Hash> = oz_leaves.iter().rev().copied().collect();
+    let tree = MerkleTree::new(reversed);
+    assert_eq!(tree.root(), Some(parse_hash(OZ_ROOT_8).unwrap()));
+
+    let multi_proof = tree.generate_multi_proof(&[7 - 1, 7 - 2, 7 - 5]).unwrap();
+    assert_eq!(
+        multi_proof.leaves,
+        vec![oz_leaves[1], oz_leaves[2], oz_leaves[5]]
+    );
+    assert_eq!(multi_proof.proof, hashes(&OZ_PROOF_8));
+    assert_eq!(multi_proof.proof_flags, OZ_FLAGS_8);
+    assert!(multi_proof.verify(&parse_hash(OZ_ROOT_8).unwrap()));
+}
+
+#[test]
+fn verifies_openzeppelin_multi_proofs() {
+    // the tree of 5 leaves of OpenZeppelin is complete, not promoted, only the verifier is shared
+    let leaves = leaves(5);
+    let root = parse_hash(OZ_ROOT_5).unwrap();
+    let oz_leaves = [leaves[0], leaves[3], leaves[4]];
+    let proof = hashes(&OZ_PROOF_5);
+    assert!(verify_multi_proof(&root, &oz_leaves, &proof, &OZ_FLAGS_5));
+    assert!(!verify_multi_proof(
+        &root,
+        &[leaves[0], leaves[3], leaves[2]],
+        &proof,
+        &OZ_FLAGS_5
+    ));
+}
+
+#[test]
+fn proofs_verify_for_every_subset_of_small_trees() {
+    for size in 1..=9 {
+        let tree = MerkleTree::new(leaves(size));
+        let root = tree.root().unwrap();
+        for indices in subsets(size) {
+            match tree.generate_multi_proof(&indices) {
+                Ok(multi_proof) => {
+                    assert!(
+                        multi_proof.verify(&root),
+                        "leaves {:?} of {}",
+                        indices,
+                        size
+                    );
+                    let mut sorted = multi_proof.indices.clone();
+                    sorted.sort();
+                    assert_eq!(sorted, indices);
+                }
+                Err(e) => assert_eq!(e, MerkleError::UnsupportedLayout),
+            }
+        }
+    }
+}
+
+#[test]
+fn siblings_are_hashed_once() {
+    // two neighbours share every sibling above them
+    let tree = MerkleTree::new(leaves(16));
+    let multi_proof = tree.generate_multi_proof(&[4, 5]).unwrap();
+    assert_eq!(multi_proof.proof.len(), 3);
+    assert_eq!(multi_proof.proof_flags.len(), 4);
+    assert_eq!(multi_proof.indices, vec![5, 4]);
+
+    // every leaf needs no sibling at all, one single leaf is a plain proof
+    let multi_proof = tree
+        .generate_multi_proof(&(0..16).collect::<Vec<_>>())
+        .unwrap();
+    assert!(multi_proof.proof.is_empty());
+    assert!(multi_proof.proof_flags.iter().all(|&flag| flag));
+    let multi_proof = tree.generate_multi_proof(&[9, 9]).unwrap();
+    assert_eq!(multi_proof.indices, vec![9]);
+    assert_eq!(Some(multi_proof.proof.clone()), tree.generate_proof(9));
+    assert!(verify_proof(
+        &tree.root().unwrap(),
+        &tree.leaves()[9],
+        &multi_proof.proof
+    ));
+}
+
+#[test]
+fn rejects_flag_and_proof_length_mismatches() {
+    let tree = MerkleTree::new(leaves(8));
+    let root = tree.root().unwrap();
+    let multi_proof = tree.generate_multi_proof(&[1, 2, 5]).unwrap();
+    let (leaves, proof, flags) = (
+        &multi_proof.leaves,
+        &multi_proof.proof,
+        &multi_proof.proof_flags,
+    );
+
+    // one flag or one sibling too many or too few
+    let mut longer = flags.clone();
+    longer.push(false);
+    let mut extra = proof.clone();
+    extra.push(root);
+    for (proof, flags) in [
+        (proof.as_slice(), longer.as_slice()),
+        (proof, &flags[1..]),
+        (extra.as_slice(), flags.as_slice()),
+        (&proof[1..], flags),
+    ] {
+        assert_eq!(
+            process_multi_proof(leaves, proof, flags),
+            Err(MerkleError::InvalidMultiProof)
+        );
+        assert!(!verify_multi_proof(&root, leaves, proof, flags));
+    }
+
+    // the counts add up, but the flags leave the proof unused or ask for more siblings than it
+    // has
+    assert_eq!(
+        process_multi_proof(leaves, proof, &vec![true; flags.len()]),
+        Err(MerkleError::InvalidMultiProof)
+    );
+    assert_eq!(
+        process_multi_proof(leaves, proof, &vec![false; flags.len()]),
+        Err(MerkleError::InvalidMultiProof)
+    );
+
+    // without any leaf the proof is the root, and nothing is not a proof of anything
+    assert_eq!(process_multi_proof(&[], &[root], &[]), Ok(root));
+    assert_eq!(
+        process_multi_proof(&[], &[], &[]),
+        Err(MerkleError::InvalidMultiProof)
+    );
+    let empty = tree.generate_multi_proof(&[]).unwrap();
+    assert!(empty.leaves.is_empty());
+    assert!(empty.verify(&root));
+}
+
+#[test]
+fn rejects_leaves_too_far_apart_in_depth() {
+    // the fifth leaf of five is promoted twice, it sits two levels above the first four
+    let tree = MerkleTree::new(leaves(5));
+    assert_eq!(
+        tree.generate_multi_proof(&[0, 4]),
+        Err(MerkleError::UnsupportedLayout)
+    );
+    assert_eq!(
+        tree.generate_multi_proof(&[2, 3, 4]),
+        Err(MerkleError::UnsupportedLayout)
+    );
+    // leaves of one depth can always be proven together
+    assert!(tree
+        .generate_multi_proof(&[0, 1, 3])
+        .unwrap()
+        .verify(&tree.root().unwrap()));
+    assert!(tree
+        .generate_multi_proof(&[4])
+        .unwrap()
+        .verify(&tree.root().unwrap()));
+
+    // six leaves put the last two one level above the others, adjacent depths mix
+    let tree = MerkleTree::new(leaves(6));
+    assert!(tree
+        .generate_multi_proof(&[0, 5])
+        .unwrap()
+        .verify(&tree.root().unwrap()));
+
+    assert_eq!(
+        tree.generate_multi_proof(&[6]),
+        Err(MerkleError::LeafIndexOutOfBounds(6))
+    );
+    assert_eq!(
+        MerkleTree::new(vec![]).generate_multi_proof(&[0]),
+        Err(MerkleError::EmptyTree)
+    );
+}
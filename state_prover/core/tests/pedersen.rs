@@ -0,0 +1,172 @@
+mod common;
+
+use common::{exit, point};
+use k256::{ProjectivePoint, Scalar};
+use state_prover_core::{
+    amount_from_wei, amount_to_wei, commit, commit_clear_amount, generator_h, hash_to_scalar,
+    parse_amount, point_to_hex, verify_clear_commitment, CommitmentError, DepositError,
+    PendingUserDepositTx, UserDepositEvent, Withdrawal, UTXO,
+};
+
+// H = H_p(G), computed by tests/vectors/gen_vectors.py
+const GENERATOR_H: &str = "02682109d3aefab17db413340e511c835bf93d7e1df10993129dce68da5edaf0ac";
+
+fn deposit_event(amount: &str) -> UserDepositEvent {
+    UserDepositEvent {
+        txId: hex::encode([7u8; 32]),
+        amount: amount.to_string(),
+        currency: "ETH".to_string(),
+        root_block_number: 1,
+        root_blockchain: "sepolia".to_string(),
+        public_key: point("deposit key"),
+        r_g: point("deposit rG"),
+    }
+}
+
+#[test]
+fn generator_h_vector() {
+    assert_eq!(point_to_hex(&generator_h()), GENERATOR_H);
+    assert_ne!(generator_h(), ProjectivePoint::GENERATOR);
+}
+
+#[test]
+fn commit_open_identities() {
+    let (a, b) = (hash_to_scalar(b"a"), hash_to_scalar(b"b"));
+    assert_eq!(commit(5, &Scalar::ZERO), generator_h() * Scalar::from(5u64));
+    assert_eq!(
+        commit(5, &a) + commit(7, &b),
+        commit(12, &(a + b)),
+        "commitments add up"
+    );
+    assert_eq!(
+        commit(5, &a) - commit(5, &Scalar::ZERO),
+        ProjectivePoint::GENERATOR * a
+    );
+
+    let commitment = commit_clear_amount("5").unwrap();
+    assert_eq!(commitment, point_to_hex(&commit(5, &Scalar::ZERO)));
+    assert_eq!(verify_clear_commitment(&commitment, "5"), Ok(()));
+    assert_eq!(
+        verify_clear_commitment(&commitment, "6"),
+        Err(CommitmentError::Mismatch(
+            commitment.clone(),
+            "6".to_string()
+        ))
+    );
+    // a blinded commitment does not open to its clear amount
+    let blinded = point_to_hex(&commit(5, &a));
+    assert!(verify_clear_commitment(&blinded, "5").is_err());
+
+    let largest = u64::MAX.to_string();
+    let commitment = commit_clear_amount(&largest).unwrap();
+    assert_eq!(verify_clear_commitment(&commitment, &largest), Ok(()));
+}
+
+#[test]
+fn rejects_zero_amounts() {
+    // the clear commitment to zero would be the identity
+    assert_eq!(commit(0, &Scalar::ZERO), ProjectivePoint::IDENTITY);
+    assert_eq!(parse_amount("0"), Ok(0));
+    assert_eq!(
+        commit_clear_amount("0"),
+        Err(CommitmentError::InvalidAmount("0".to_string()))
+    );
+    assert_eq!(
+        verify_clear_commitment(&point("zero"), "0"),
+        Err(CommitmentError::InvalidAmount("0".to_string()))
+    );
+    assert_eq!(
+        amount_from_wei("0").and_then(|amount| commit_clear_amount(&amount.to_string())),
+        Err(CommitmentError::InvalidAmount("0".to_string()))
+    );
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let too_large = "18446744073709551616"; // 2^64
+    for amount in [
+        "", "-1", "+1", "1.5", "0x10", " 1", "1e3", "five", too_large,
+    ] {
+        assert_eq!(
+            commit_clear_amount(amount),
+            Err(CommitmentError::InvalidAmount(amount.to_string()))
+        );
+    }
+    let commitment = commit_clear_amount("5").unwrap();
+    assert_eq!(
+        verify_clear_commitment(&commitment, "5.0"),
+        Err(CommitmentError::InvalidAmount("5.0".to_string()))
+    );
+    for commitment in ["", "02aa", "zz", &"00".repeat(33)] {
+        assert_eq!(
+            verify_clear_commitment(commitment, "5"),
+            Err(CommitmentError::InvalidCommitment(commitment.to_string()))
+        );
+    }
+}
+
+#[test]
+fn converts_wei_to_units() {
+    assert_eq!(amount_from_wei("1000000000"), Ok(1));
+    // 20 ETH is above the 18.4 ETH of 64 bits of wei
+    assert_eq!(amount_from_wei("20000000000000000000"), Ok(20_000_000_000));
+    let largest = format!("{}000000000", u64::MAX);
+    assert_eq!(amount_from_wei(&largest), Ok(u64::MAX));
+    assert_eq!(amount_to_wei(u64::MAX).to_string(), largest);
+
+    let overflow = format!("{}000000000", u64::MAX as u128 + 1);
+    let beyond_u256 = "1".repeat(80);
+    for wei in [overflow.as_str(), beyond_u256.as_str()] {
+        assert_eq!(
+            amount_from_wei(wei),
+            Err(CommitmentError::AmountOverflow(wei.to_string()))
+        );
+    }
+    for wei in ["1", "1000000001", "999999999"] {
+        assert_eq!(
+            amount_from_wei(wei),
+            Err(CommitmentError::FractionalAmount(wei.to_string()))
+        );
+    }
+    for wei in ["", "-1000000000", "0x3b9aca00", "1 ETH"] {
+        assert_eq!(
+            amount_from_wei(wei),
+            Err(CommitmentError::InvalidAmount(wei.to_string()))
+        );
+    }
+}
+
+#[test]
+fn deposit_commits_to_its_amount_in_units() {
+    let tx = PendingUserDepositTx::from_user_deposit_event(deposit_event("20000000000000000000"))
+        .unwrap();
+    match &tx.output {
+        UTXO::Coinbase(coinbase) => {
+            assert_eq!(coinbase.amount, "20000000000");
+            assert_eq!(
+                verify_clear_commitment(&coinbase.commitment, &coinbase.amount),
+                Ok(())
+            );
+            assert_eq!(coinbase.utxo_hash(), Ok(coinbase.hash.clone()));
+        }
+        output => panic!("deposit output {:?}", output),
+    }
+    assert_eq!(tx.tx_hash(), Ok(tx.hash.clone()));
+
+    for (wei, error) in [
+        ("1", CommitmentError::FractionalAmount("1".to_string())),
+        ("0", CommitmentError::InvalidAmount("0".to_string())),
+    ] {
+        assert_eq!(
+            PendingUserDepositTx::from_user_deposit_event(deposit_event(wei)).map(|tx| tx.hash),
+            Err(DepositError::InvalidAmount(error))
+        );
+    }
+}
+
+#[test]
+fn withdrawal_pays_wei() {
+    let withdrawal = Withdrawal::from_exit(&exit()).unwrap();
+    assert_eq!(withdrawal.amount, amount_to_wei(5));
+    assert_eq!(withdrawal.amount.to_string(), "5000000000");
+}
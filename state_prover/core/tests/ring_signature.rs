@@ -0,0 +1,291 @@
+mod common;
+
+use common::ringct::{ring_ct, ring_ct_ring, set_signature, sign, signature};
+use common::{apply, deposit, genesis, withdrawal};
+use k256::{ProjectivePoint, Scalar};
+use state_prover_core::transition::{apply_transactions, TransitionError};
+use state_prover_core::{
+    commit, decode_signature, hash_to_scalar, point_to_hex, scalar_to_hex, verify_ring_signature,
+    Generators, MlsagError, PendingRingCT, PendingTransaction, MLSAG, UTXO,
+};
+
+fn secret(label: &str) -> Scalar {
+    hash_to_scalar(label.as_bytes())
+}
+
+fn members() -> Vec<UTXO> {
+    vec![
+        deposit("alice", 10),
+        deposit("bob", 20),
+        deposit("carol", 30),
+    ]
+}
+
+/// Bob spends his 20 with a blinded pseudo commitment: `C_bob - C_pseudo = -b * G`.
+fn transaction() -> PendingRingCT {
+    let blinding = secret("pseudo blinding");
+    let pseudo = commit(20, &blinding);
+    ring_ct(
+        &members(),
+        1,
+        &secret("bob"),
+        &-blinding,
+        &pseudo,
+        vec![withdrawal("dave", 20)],
+        0,
+    )
+}
+
+fn verify(tx: &PendingRingCT) -> Result<(), MlsagError> {
+    let members = members();
+    let inputs: Vec<&UTXO> = members.iter().collect();
+    verify_ring_signature(tx, &inputs).map(|_| ())
+}
+
+#[test]
+fn sign_verify_round_trip() {
+    // every ring size and every signer position
+    for rows in 1..4 {
+        for index in 0..rows {
+            let secrets: Vec<Vec<Scalar>> = (0..rows)
+                .map(|row| {
+                    (0..3)
+                        .map(|column| secret(&format!("{} {}", row, column)))
+                        .collect()
+                })
+                .collect();
+            let ring: Vec<Vec<ProjectivePoint>> = secrets
+                .iter()
+                .map(|row| row.iter().map(|s| ProjectivePoint::GENERATOR * s).collect())
+                .collect();
+            for linkable in 1..3 {
+                let signature = sign("message", &ring, index, &secrets[index], linkable);
+                assert_eq!(signature.verify(), Ok(()));
+                let points: Vec<ProjectivePoint> = ring.iter().flatten().copied().collect();
+                assert_eq!(signature.verify_ring(&points), Ok(()));
+            }
+        }
+    }
+
+    let tx = transaction();
+    assert_eq!(verify(&tx), Ok(()));
+}
+
+#[test]
+fn commitment_column_may_be_the_identity() {
+    // spending a coinbase into exits only: no blinding factor anywhere, so z = 0
+    let tx = ring_ct(
+        &members(),
+        0,
+        &secret("alice"),
+        &Scalar::ZERO,
+        &commit(10, &Scalar::ZERO),
+        vec![withdrawal("dave", 10)],
+        0,
+    );
+    assert_eq!(verify(&tx), Ok(()));
+}
+
+#[test]
+fn rejects_tampered_ring_member() {
+    let tx = transaction();
+
+    // a member replaced in the set the transaction references
+    let mut members = members();
+    members[2] = deposit("eve", 30);
+    let inputs: Vec<&UTXO> = members.iter().collect();
+    assert_eq!(
+        verify_ring_signature(&tx, &inputs).map(|_| ()),
+        Err(MlsagError::RingMismatch(2))
+    );
+
+    // the same commitment under another key
+    let mut members = self::members();
+    if let UTXO::Coinbase(utxo) = &mut members[0] {
+        utxo.public_key = common::point("eve");
+    }
+    let inputs: Vec<&UTXO> = members.iter().collect();
+    assert_eq!(
+        verify_ring_signature(&tx, &inputs).map(|_| ()),
+        Err(MlsagError::RingMismatch(0))
+    );
+
+    // a signature over another pseudo commitment does not match the ring of the transaction
+    let mut tx = transaction();
+    tx.pseudo_commitments = vec![point_to_hex(&commit(20, &Scalar::ONE))];
+    assert_eq!(verify(&tx), Err(MlsagError::RingMismatch(0)));
+
+    // the ring of the signature is checked even when it still closes
+    let mut tx = transaction();
+    let pseudo = commit(20, &Scalar::ONE);
+    let mut members = self::members();
+    members[1] = deposit("bob", 21);
+    let forged = sign(
+        &tx.hash,
+        &ring_ct_ring(&members, &pseudo),
+        0,
+        &[secret("alice"), -Scalar::ONE],
+        1,
+    );
+    set_signature(&mut tx, &forged);
+    assert!(matches!(verify(&tx), Err(MlsagError::RingMismatch(_))));
+}
+
+#[test]
+fn rejects_tampered_response() {
+    for row in 0..3 {
+        for column in 0..2 {
+            let mut tx = transaction();
+            let mut signature = signature(&tx);
+            signature.responses[row][column] = scalar_to_hex(&Scalar::ONE);
+            set_signature(&mut tx, &signature);
+            assert_eq!(verify(&tx), Err(MlsagError::RingNotClosed(2)));
+        }
+    }
+
+    let mut tx = transaction();
+    let mut signature = signature(&tx);
+    signature.responses[1][1] = "zz".to_string();
+    set_signature(&mut tx, &signature);
+    assert_eq!(verify(&tx), Err(MlsagError::InvalidResponse(1, 1)));
+}
+
+#[test]
+fn rejects_wrong_message() {
+    // the signature of another transaction
+    let mut tx = transaction();
+    tx.fee = 1;
+    tx.hash = tx.tx_hash().unwrap();
+    assert_eq!(verify(&tx), Err(MlsagError::MessageMismatch));
+
+    // a signature relabelled with the new hash does not close
+    let mut signature = signature(&tx);
+    signature.message = tx.hash.clone();
+    set_signature(&mut tx, &signature);
+    assert_eq!(verify(&tx), Err(MlsagError::RingNotClosed(2)));
+}
+
+#[test]
+fn rejects_wrong_shape() {
+    // a signature over the public keys only, without the commitment column
+    let mut tx = transaction();
+    let ring: Vec<Vec<ProjectivePoint>> = ring_ct_ring(&members(), &ProjectivePoint::IDENTITY)
+        .into_iter()
+        .map(|row| vec![row[0]])
+        .collect();
+    let single_column = sign(&tx.hash, &ring, 1, &[secret("bob")], 1);
+    set_signature(&mut tx, &single_column);
+    assert_eq!(verify(&tx), Err(MlsagError::ColumnCount(1)));
+
+    // a key image on the commitment column too
+    let mut tx = transaction();
+    let mut signature = signature(&tx);
+    signature.key_images.push(signature.key_images[0].clone());
+    set_signature(&mut tx, &signature);
+    assert_eq!(verify(&tx), Err(MlsagError::KeyImageCount(2)));
+
+    // one row per input
+    let tx = transaction();
+    let members = members();
+    let inputs: Vec<&UTXO> = members[..2].iter().collect();
+    assert_eq!(
+        verify_ring_signature(&tx, &inputs).map(|_| ()),
+        Err(MlsagError::RingShape(6, 2))
+    );
+
+    // a single pseudo commitment shared by the rows
+    let mut tx = transaction();
+    tx.pseudo_commitments.push(tx.pseudo_commitments[0].clone());
+    assert_eq!(verify(&tx), Err(MlsagError::PseudoCommitmentCount(2)));
+}
+
+#[test]
+fn decodes_the_bincode_encoding() {
+    let tx = transaction();
+    let bytes = hex::decode(&tx.signature).unwrap();
+    assert_eq!(MLSAG::decode(&bytes), Some(signature(&tx)));
+    assert_eq!(decode_signature(&tx), Ok(signature(&tx)));
+    let empty = MLSAG::default();
+    assert_eq!(MLSAG::decode(&empty.to_bytes().unwrap()), Some(empty));
+
+    // truncated, trailing bytes, a count beyond the input and a string that is not UTF-8
+    assert_eq!(MLSAG::decode(&bytes[..bytes.len() - 1]), None);
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(MLSAG::decode(&trailing), None);
+    let mut count = bytes.clone();
+    let ring = 8 + tx.hash.len();
+    count[ring..ring + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(MLSAG::decode(&count), None);
+    let mut utf8 = bytes.clone();
+    utf8[8] = 0xff;
+    assert_eq!(MLSAG::decode(&utf8), None);
+
+    let mut tx = transaction();
+    tx.signature.push_str("00");
+    assert_eq!(decode_signature(&tx), Err(MlsagError::InvalidEncoding));
+}
+
+#[test]
+fn rejects_reused_key_image() {
+    let members = members();
+    // Bob spends the same UTXO twice, in two different transactions
+    let spend = |fee: u64| {
+        ring_ct(
+            &members,
+            1,
+            &secret("bob"),
+            &Scalar::ZERO,
+            &commit(20, &Scalar::ZERO),
+            vec![withdrawal(&format!("dave {}", fee), 20 - fee)],
+            fee,
+        )
+    };
+    let (first, second) = (spend(0), spend(1));
+    let key_image = signature(&first).key_images[0].clone();
+    assert_eq!(signature(&second).key_images[0], key_image);
+    let double_spend = |result: Result<_, TransitionError>, spent_by: &str| {
+        matches!(
+            result,
+            Err(TransitionError::DoubleSpend(tx, image)) if tx == spent_by && image == key_image
+        )
+    };
+
+    let genesis = genesis(&members);
+    let mut state = genesis.clone();
+    apply(&mut state, &[PendingTransaction::PendingRingCTx(first)]).unwrap();
+    assert!(state.is_spent(&key_image));
+
+    // the host has no witness to spend it again, in a later block or in the same one
+    let transactions = [PendingTransaction::PendingRingCTx(second.clone())];
+    let result = state.clone().apply_block(&transactions);
+    assert!(double_spend(result.map(|_| ()), &second.hash));
+    let third = spend(2);
+    let both = [
+        transactions[0].clone(),
+        PendingTransaction::PendingRingCTx(third.clone()),
+    ];
+    let result = genesis.clone().apply_block(&both);
+    assert!(double_spend(result.map(|_| ()), &third.hash));
+
+    // and the guest rejects a witness proving it absent from a tree where it was never spent
+    let generators = Generators::new();
+    let forged = genesis.clone().apply_block(&transactions).unwrap();
+    let result = apply_transactions(&generators, &state.commitment(), &transactions, &forged);
+    assert!(double_spend(result.map(|_| ()), &second.hash));
+
+    // the witnesses must be the ones of the key images of the block
+    let mut missing = forged.clone();
+    missing.key_images.clear();
+    let result = apply_transactions(&generators, &genesis.commitment(), &transactions, &missing);
+    assert!(matches!(result, Err(TransitionError::MissingWitness(_))));
+    let result = apply_transactions(&generators, &genesis.commitment(), &[], &forged);
+    assert!(matches!(result, Err(TransitionError::UnexpectedWitness(_))));
+
+    // another key image for the same key does not close the ring
+    let mut tx = transaction();
+    let mut signature = signature(&tx);
+    signature.key_images[0] = point_to_hex(&(ProjectivePoint::GENERATOR * secret("other")));
+    set_signature(&mut tx, &signature);
+    assert_eq!(verify(&tx), Err(MlsagError::RingNotClosed(2)));
+}
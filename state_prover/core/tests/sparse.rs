@@ -0,0 +1,177 @@
+use state_prover_core::{
+    apply_witnesses, keccak256, verify_exclusion, verify_inclusion, Hash, MerkleError,
+    SparseMerkleTree, SparseProof, EMPTY_NODE,
+};
+
+// Roots of `entries()` with and without the key "a", computed by tests/vectors/gen_vectors.py
+const ROOT: &str = "7ec17b94beff6df72aa9ff95f524f8a8e4ad8e46dfcae7db35659d678bd42dd8";
+const ROOT_WITHOUT_A: &str = "a5623c4de84d777a052bf651f8622dc30cdfa5910b7c88c60cb9e3a18556278d";
+
+fn key(label: &str) -> Hash {
+    keccak256(label.as_bytes())
+}
+
+/// Two random keys, and a neighbour of the first one that only differs in the last bit, so that
+/// their paths share every node but the leaves.
+fn entries() -> Vec<(Hash, Hash)> {
+    let mut neighbour = key("a");
+    neighbour[31] ^= 1;
+    vec![
+        (key("a"), EMPTY_NODE),
+        (key("b"), EMPTY_NODE),
+        (neighbour, key("value")),
+    ]
+}
+
+#[test]
+fn golden_roots() {
+    let mut tree: SparseMerkleTree = entries().into_iter().collect();
+    assert_eq!(hex::encode(tree.root()), ROOT);
+    tree.remove(&key("a"));
+    assert_eq!(hex::encode(tree.root()), ROOT_WITHOUT_A);
+    assert_eq!(SparseMerkleTree::new().root(), EMPTY_NODE);
+}
+
+#[test]
+fn insert_remove_prove_round_trip() {
+    let mut tree = SparseMerkleTree::new();
+    for (key, value) in entries() {
+        assert_eq!(tree.insert(key, value), None);
+    }
+    let root = tree.root();
+    for (key, value) in entries() {
+        assert_eq!(tree.get(&key), Some(&value));
+        assert!(verify_inclusion(&root, &key, &value, &tree.prove(&key)));
+    }
+
+    // the root does not depend on the order of the insertions
+    let reversed: SparseMerkleTree = entries().into_iter().rev().collect();
+    assert_eq!(reversed.root(), root);
+
+    // setting a key again replaces its value
+    assert_eq!(tree.insert(key("b"), key("new")), Some(EMPTY_NODE));
+    assert_ne!(tree.root(), root);
+    assert_eq!(tree.insert(key("b"), EMPTY_NODE), Some(key("new")));
+    assert_eq!(tree.root(), root);
+
+    // removing every key gives back the empty tree
+    for (key, value) in entries() {
+        assert_eq!(tree.remove(&key), Some(value));
+        assert!(verify_exclusion(&tree.root(), &key, &tree.prove(&key)));
+    }
+    assert_eq!(tree.remove(&key("a")), None);
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), EMPTY_NODE);
+}
+
+#[test]
+fn exclusion_proofs() {
+    let tree: SparseMerkleTree = entries().into_iter().collect();
+    let root = tree.root();
+    let absent = key("c");
+    let proof = tree.prove(&absent);
+    assert!(verify_exclusion(&root, &absent, &proof));
+    assert!(!verify_inclusion(&root, &absent, &EMPTY_NODE, &proof));
+
+    // a set key has no exclusion proof, not even its own path
+    let proof = tree.prove(&key("a"));
+    assert!(!verify_exclusion(&root, &key("a"), &proof));
+    assert!(!verify_inclusion(&root, &key("a"), &key("value"), &proof));
+
+    // the neighbour path has a single sibling at the bottom besides the top one
+    let mut neighbour = key("a");
+    neighbour[31] ^= 1;
+    let proof = tree.prove(&neighbour);
+    assert_eq!(proof.siblings.len(), 2);
+    assert_eq!(proof.bitmap[31] & 1, 1);
+
+    // every sibling and every bit of the bitmap counts
+    let proof = tree.prove(&absent);
+    let mut tampered = proof.clone();
+    tampered.siblings[0][0] ^= 1;
+    assert!(!verify_exclusion(&root, &absent, &tampered));
+    let mut tampered = proof.clone();
+    tampered.siblings.push(key("extra"));
+    assert_eq!(
+        tampered.compute_root(&absent, &EMPTY_NODE),
+        Err(MerkleError::MalformedProof(
+            proof.siblings.len(),
+            proof.siblings.len() + 1
+        ))
+    );
+    let mut tampered = proof.clone();
+    tampered.bitmap[0] ^= 0x80;
+    assert!(tampered.compute_root(&absent, &EMPTY_NODE).is_err());
+
+    // the empty tree proves every key absent with an empty proof
+    assert!(verify_exclusion(
+        &EMPTY_NODE,
+        &absent,
+        &SparseProof::default()
+    ));
+}
+
+#[test]
+fn witness_sequence_reproduces_the_final_root() {
+    let mut tree: SparseMerkleTree = entries().into_iter().collect();
+    let initial = tree.root();
+    let witnesses = vec![
+        tree.update(key("c"), Some(EMPTY_NODE)), // insert
+        tree.update(key("a"), Some(EMPTY_NODE)), // inclusion, the root does not move
+        tree.update(key("b"), Some(key("new"))), // update
+        tree.update(key("a"), None),             // delete
+        tree.update(key("d"), None),             // exclusion
+        tree.update(key("a"), Some(EMPTY_NODE)), // insert back
+    ];
+    assert_eq!(witnesses[0].old_value, None);
+    assert_eq!(witnesses[3].old_value, Some(EMPTY_NODE));
+    assert_eq!(witnesses[4].old_value, None);
+    assert_eq!(apply_witnesses(&initial, &witnesses), Ok(tree.root()));
+    assert_eq!(apply_witnesses(&initial, &[]), Ok(initial));
+
+    // each witness moves the root to the next one
+    let mut root = initial;
+    for witness in &witnesses {
+        let next = witness.apply(&root).unwrap();
+        if witness.old_value == witness.new_value {
+            assert_eq!(next, root);
+        }
+        root = next;
+    }
+    assert_eq!(root, tree.root());
+}
+
+#[test]
+fn rejects_wrong_root() {
+    let mut tree: SparseMerkleTree = entries().into_iter().collect();
+    let initial = tree.root();
+    let witnesses = vec![
+        tree.update(key("c"), Some(EMPTY_NODE)),
+        tree.update(key("a"), None),
+    ];
+
+    // applied to another root, or out of order
+    let other = key("other root");
+    assert!(matches!(
+        apply_witnesses(&other, &witnesses),
+        Err(MerkleError::RootMismatch(root, _)) if root == other
+    ));
+    assert!(matches!(
+        apply_witnesses(&initial, &[witnesses[1].clone(), witnesses[0].clone()]),
+        Err(MerkleError::RootMismatch(..))
+    ));
+
+    // a witness claiming another old value
+    let mut absent = witnesses[1].clone();
+    absent.old_value = None;
+    assert!(matches!(
+        apply_witnesses(&initial, &[witnesses[0].clone(), absent]),
+        Err(MerkleError::RootMismatch(..))
+    ));
+    let mut present = witnesses[0].clone();
+    present.old_value = Some(EMPTY_NODE);
+    assert!(matches!(
+        present.apply(&initial),
+        Err(MerkleError::RootMismatch(..))
+    ));
+}
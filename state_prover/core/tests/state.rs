@@ -0,0 +1,311 @@
+mod common;
+
+use common::ringct::{ring_ct, signature};
+use common::{apply, deposit_tx, genesis, with_tx_id, withdrawal};
+use k256::Scalar;
+use state_prover_core::transition::{
+    apply_transactions, batch_withdrawals, execute, TransitionError, EMPTY_ROOT,
+};
+use state_prover_core::{
+    commit, hash_to_scalar, keccak256, Block, BlockHeader, BlockWitness, Generators, Inputs,
+    MerkleAccumulator, MerkleError, PendingTransaction, State, StateCommitment, UTXO,
+};
+
+fn secret(label: &str) -> Scalar {
+    hash_to_scalar(label.as_bytes())
+}
+
+fn output(tx: &PendingTransaction) -> UTXO {
+    tx.outputs()[0].clone()
+}
+
+/// Alice spends her deposit of 10 to `outputs`, in a ring with Bob's.
+fn spend(members: &[UTXO], outputs: Vec<UTXO>) -> PendingTransaction {
+    PendingTransaction::PendingRingCTx(ring_ct(
+        members,
+        0,
+        &secret("alice"),
+        &Scalar::ZERO,
+        &commit(10, &Scalar::ZERO),
+        outputs,
+        0,
+    ))
+}
+
+/// Block of `transactions`, the header is not checked by `apply_blocks`.
+fn block(transactions: &[PendingTransaction]) -> Block {
+    Block {
+        header: BlockHeader {
+            height: 1,
+            parent_hash: EMPTY_ROOT.to_string(),
+            timestamp: 0,
+            tx_root: EMPTY_ROOT.to_string(),
+            pre_utxo_root: EMPTY_ROOT.to_string(),
+            post_utxo_root: EMPTY_ROOT.to_string(),
+            sequencer: "0x0000000000000000000000000000000000000000".to_string(),
+        },
+        transactions: transactions.to_vec(),
+    }
+}
+
+fn tx_id(tx: &PendingTransaction) -> String {
+    match tx {
+        PendingTransaction::PendingDeposit(deposit) => deposit.txId.clone(),
+        PendingTransaction::PendingRingCTx(_) => panic!("not a deposit"),
+    }
+}
+
+fn key_image(tx: &PendingTransaction) -> String {
+    match tx {
+        PendingTransaction::PendingRingCTx(ring) => signature(ring).key_images[0].clone(),
+        PendingTransaction::PendingDeposit(_) => panic!("deposits have no key image"),
+    }
+}
+
+fn check(
+    state: &StateCommitment,
+    transactions: &[PendingTransaction],
+    witness: &BlockWitness,
+) -> Result<StateCommitment, TransitionError> {
+    apply_transactions(&Generators::new(), state, transactions, witness)
+}
+
+#[test]
+fn witnesses_follow_the_host_state() {
+    let (alice, bob) = (deposit_tx("alice", 10), deposit_tx("bob", 20));
+    let members = [output(&alice), output(&bob)];
+    let mut state = State::new();
+    let empty = state.commitment();
+    assert_eq!(empty, StateCommitment::default());
+
+    // Alice spends her deposit in the block it is made
+    let first = [alice, bob];
+    let second = [spend(&members, vec![withdrawal("carol", 10)])];
+    let witnesses = state
+        .apply_blocks(&[block(&first), block(&second)])
+        .unwrap();
+    assert_eq!(witnesses.len(), 2);
+    assert!(witnesses[0].members.is_empty());
+    assert_eq!(witnesses[1].members, members);
+    // two ring members and the exit, then the key image
+    assert_eq!(witnesses[1].utxos.len(), 3);
+    assert_eq!(witnesses[1].key_images.len(), 1);
+
+    let after_first = check(&empty, &first, &witnesses[0]).unwrap();
+    let after_second = check(&after_first, &second, &witnesses[1]).unwrap();
+    assert_eq!(after_second, state.commitment());
+    // the exit left the set, only the key image changed
+    assert_eq!(after_second.utxo_root, after_first.utxo_root);
+    assert_ne!(after_second.key_image_root, after_first.key_image_root);
+
+    let mut utxos: Vec<UTXO> = state.utxos().cloned().collect();
+    utxos.sort_by_key(UTXO::get_hash);
+    let mut expected = members.to_vec();
+    expected.sort_by_key(UTXO::get_hash);
+    assert_eq!(utxos, expected);
+    assert!(state.is_spent(&key_image(&second[0])));
+}
+
+#[test]
+fn ring_member_must_be_in_the_set() {
+    let (alice, bob) = (deposit_tx("alice", 10), deposit_tx("bob", 20));
+    let members = [output(&alice), output(&bob)];
+    let tx = [spend(&members, vec![withdrawal("carol", 10)])];
+
+    // Bob's deposit never happened
+    let state = genesis(&members[..1]);
+    assert!(matches!(
+        state.clone().apply_block(&tx),
+        Err(TransitionError::UnknownInput(_, input)) if input == members[1].get_hash()
+    ));
+
+    // a witness proving it included in another tree does not match the root
+    let mut forged = genesis(&members);
+    let witness = forged.apply_block(&tx).unwrap();
+    assert!(matches!(
+        check(&state.commitment(), &tx, &witness),
+        Err(TransitionError::UnknownInput(hash, _)) if hash == tx[0].get_hash()
+    ));
+
+    // the content of a member comes with the witness and must match its hash
+    let mut state = genesis(&members);
+    let commitment = state.commitment();
+    let witness = state.apply_block(&tx).unwrap();
+    let mut missing = witness.clone();
+    missing.members.pop();
+    assert!(matches!(
+        check(&commitment, &tx, &missing),
+        Err(TransitionError::UnknownInput(_, input)) if input == members[1].get_hash()
+    ));
+    let mut tampered = witness.clone();
+    if let UTXO::Coinbase(coinbase) = &mut tampered.members[1] {
+        coinbase.amount = "1000".to_string();
+    }
+    assert!(matches!(
+        check(&commitment, &tx, &tampered),
+        Err(TransitionError::InvalidUTXOHash(claimed, _)) if claimed == members[1].get_hash()
+    ));
+    assert_eq!(
+        check(&commitment, &tx, &witness).unwrap(),
+        state.commitment()
+    );
+}
+
+#[test]
+fn outputs_must_be_new() {
+    let alice = deposit_tx("alice", 10);
+    let hash = output(&alice).get_hash();
+    let mut state = genesis(&[]);
+    apply(&mut state, std::slice::from_ref(&alice)).unwrap();
+
+    // the host refuses to create it again from another deposit, and leaves its state unchanged
+    let before = state.commitment();
+    let copy = with_tx_id(&alice, &hex::encode(keccak256(b"copy")));
+    assert!(matches!(
+        state.apply_block(&[deposit_tx("bob", 20), copy.clone()]),
+        Err(TransitionError::DuplicateOutput(..))
+    ));
+    assert_eq!(state.commitment(), before);
+
+    // the guest rejects a witness proving it absent from another tree, whose applied deposits
+    // are the same
+    let mut other = genesis(&[]);
+    apply(
+        &mut other,
+        &[with_tx_id(&deposit_tx("dave", 10), &tx_id(&alice))],
+    )
+    .unwrap();
+    let witness = other.apply_block(std::slice::from_ref(&copy)).unwrap();
+    assert!(matches!(
+        check(&before, &[copy], &witness),
+        Err(TransitionError::DuplicateOutput(_, duplicate)) if duplicate == hash
+    ));
+}
+
+#[test]
+fn exits_are_withdrawn_once_per_batch() {
+    let members = [
+        output(&deposit_tx("alice", 10)),
+        output(&deposit_tx("bob", 20)),
+    ];
+    let exit = withdrawal("carol", 5);
+
+    // two identical exits of a block are both proven absent from the set
+    let mut state = genesis(&members);
+    let twice = [spend(&members, vec![exit.clone(), exit.clone()])];
+    apply(&mut state, &twice).unwrap();
+    assert!(matches!(
+        batch_withdrawals(&[block(&twice)]),
+        Err(TransitionError::DuplicateOutput(tx, duplicate))
+            if tx == twice[0].get_hash() && duplicate == exit.get_hash()
+    ));
+
+    // so are the same exit of two blocks, each valid on its own
+    let mut state = genesis(&members);
+    let first = [spend(&members, vec![exit.clone(), withdrawal("dave", 5)])];
+    let second = [PendingTransaction::PendingRingCTx(ring_ct(
+        &members,
+        1,
+        &secret("bob"),
+        &Scalar::ZERO,
+        &commit(20, &Scalar::ZERO),
+        vec![exit.clone(), withdrawal("erin", 15)],
+        0,
+    ))];
+    apply(&mut state, &first).unwrap();
+    apply(&mut state, &second).unwrap();
+    assert_eq!(batch_withdrawals(&[block(&first)]).unwrap().len(), 2);
+    assert!(matches!(
+        batch_withdrawals(&[block(&first), block(&second)]),
+        Err(TransitionError::DuplicateOutput(tx, duplicate))
+            if tx == second[0].get_hash() && duplicate == exit.get_hash()
+    ));
+}
+
+#[test]
+fn witnesses_must_match_the_transactions() {
+    let alice = deposit_tx("alice", 10);
+    let bob = deposit_tx("bob", 20);
+    let state = genesis(&[]);
+    let commitment = state.commitment();
+    let witness = state
+        .clone()
+        .apply_block(std::slice::from_ref(&alice))
+        .unwrap();
+    let mut other = state
+        .clone()
+        .apply_block(std::slice::from_ref(&bob))
+        .unwrap();
+
+    assert!(matches!(
+        check(
+            &commitment,
+            std::slice::from_ref(&alice),
+            &BlockWitness::default()
+        ),
+        Err(TransitionError::MissingWitness(_))
+    ));
+    assert!(matches!(
+        check(&commitment, std::slice::from_ref(&alice), &other),
+        Err(TransitionError::UnexpectedWitness(key)) if key == tx_id(&bob)
+    ));
+    other.deposits = witness.deposits.clone();
+    assert!(matches!(
+        check(&commitment, std::slice::from_ref(&alice), &other),
+        Err(TransitionError::UnexpectedWitness(key)) if key == output(&bob).get_hash()
+    ));
+    assert!(matches!(
+        check(&commitment, &[], &witness),
+        Err(TransitionError::UnexpectedWitness(_))
+    ));
+    // the proof of a witness must be well formed
+    let mut malformed = witness.clone();
+    malformed.utxos[0].proof.siblings.push([1u8; 32]);
+    assert!(matches!(
+        check(&commitment, std::slice::from_ref(&alice), &malformed),
+        Err(TransitionError::InvalidWitness(
+            _,
+            MerkleError::MalformedProof(0, 1)
+        ))
+    ));
+    assert!(check(&commitment, &[alice], &witness).is_ok());
+}
+
+#[test]
+fn state_root() {
+    // keccak256(utxo_root || key_image_root || deposit_root)
+    assert_eq!(StateCommitment::default().root(), Ok(keccak256(&[0u8; 96])));
+    let commitment = StateCommitment {
+        utxo_root: "root".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        commitment.root(),
+        Err(MerkleError::InvalidHash("root".to_string()))
+    );
+    assert!(matches!(
+        check(&commitment, &[], &BlockWitness::default()),
+        Err(TransitionError::InvalidRoot(root)) if root == "root"
+    ));
+}
+
+#[test]
+fn invalid_transitions_report_both_roots() {
+    // the input claims a block root at t the frontier does not have
+    let frontier = MerkleAccumulator::new();
+    let computed = hex::encode(frontier.root().unwrap());
+    let claimed = hex::encode(keccak256(b"other chain"));
+    let input = Inputs {
+        state_t_1: EMPTY_ROOT.to_string(),
+        state_t: claimed.clone(),
+        block_frontier: frontier,
+        parent_proof: vec![],
+        blocks: vec![block(&[])],
+        state: StateCommitment::default(),
+        witnesses: vec![BlockWitness::default()],
+    };
+    assert!(matches!(
+        execute(&input),
+        Err(TransitionError::RootMismatch(expected, root)) if expected == claimed && root == computed
+    ));
+}
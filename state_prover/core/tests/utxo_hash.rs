@@ -0,0 +1,161 @@
+mod common;
+
+use common::{coinbase, exit, payment, point, tx_hash, uncompressed};
+use state_prover_core::{EncodingError, ExitUTXO, UTXO};
+
+// Golden vectors of the canonical UTXO hash, any other implementation must reproduce them. They
+// are computed by tests/vectors/gen_vectors.py, an ABI encoder that does not share code with
+// this crate.
+const PAYMENT_HASH: &str = "9953198bd977a8ab6cb681df143d978a0f93a74d7543c40aacd98cc9a492ea0d";
+const EXIT_HASH: &str = "4ab3981f156a354282566cb106a0d3d05f95a60526031a6cb0938b316025aaa0";
+const COINBASE_HASH: &str = "a19bdd409b6946533b630659c4ac954e654853fe5c7ecd3b224df9603ef6e488";
+
+#[test]
+fn golden_vectors() {
+    assert_eq!(payment().hash, PAYMENT_HASH);
+    assert_eq!(exit().hash, EXIT_HASH);
+    assert_eq!(coinbase().hash, COINBASE_HASH);
+    assert_eq!(
+        UTXO::Payment(Box::new(payment())).utxo_hash().unwrap(),
+        PAYMENT_HASH
+    );
+    assert_eq!(UTXO::Exit(exit()).utxo_hash().unwrap(), EXIT_HASH);
+    assert_eq!(
+        UTXO::Coinbase(coinbase()).utxo_hash().unwrap(),
+        COINBASE_HASH
+    );
+}
+
+#[test]
+fn hash_ignores_the_stored_hash() {
+    let mut utxo = coinbase();
+    utxo.hash = "00".repeat(32);
+    assert_eq!(utxo.utxo_hash().unwrap(), COINBASE_HASH);
+}
+
+#[test]
+fn hash_ignores_the_hex_spelling() {
+    // prefix, case, point encoding and leading zeros do not change the hash
+    let mut utxo = payment();
+    utxo.version = "1".to_string();
+    utxo.transaction_hash = format!("0x{}", tx_hash().to_uppercase());
+    utxo.public_key = uncompressed(&utxo.public_key);
+    utxo.commitment = format!("0x{}", utxo.commitment.to_uppercase());
+    utxo.amount = format!("0x{}", utxo.amount);
+    utxo.rangeProof.tx = format!("0x{}", utxo.rangeProof.tx);
+    utxo.rangeProof.ind[2].L = uncompressed(&utxo.rangeProof.ind[2].L);
+    assert_eq!(utxo.utxo_hash().unwrap(), PAYMENT_HASH);
+
+    let mut utxo = coinbase();
+    utxo.version = "0x0000000000000001".to_string();
+    utxo.transaction_hash = format!("0x{}", utxo.transaction_hash);
+    assert_eq!(utxo.utxo_hash().unwrap(), COINBASE_HASH);
+
+    let mut utxo = exit();
+    utxo.amount = "005".to_string();
+    utxo.public_key = uncompressed(&utxo.public_key);
+    assert_eq!(utxo.utxo_hash().unwrap(), EXIT_HASH);
+}
+
+#[test]
+fn every_field_is_hashed() {
+    let mut utxo = payment();
+    utxo.output_index = 4;
+    assert_ne!(utxo.utxo_hash().unwrap(), PAYMENT_HASH);
+
+    let mut utxo = payment();
+    utxo.rangeProof.ind[1].R = point("R6");
+    assert_ne!(utxo.utxo_hash().unwrap(), PAYMENT_HASH);
+
+    // the order of the inner product rounds is part of the hash
+    let mut utxo = payment();
+    utxo.rangeProof.ind.swap(0, 1);
+    assert_ne!(utxo.utxo_hash().unwrap(), PAYMENT_HASH);
+
+    let mut utxo = exit();
+    utxo.exitChain = "mainnet".to_string();
+    assert_ne!(utxo.utxo_hash().unwrap(), EXIT_HASH);
+}
+
+#[test]
+fn missing_unlock_time_differs_from_zero() {
+    let mut utxo = coinbase();
+    utxo.unlock_time = Some(0);
+    assert_ne!(utxo.utxo_hash().unwrap(), COINBASE_HASH);
+}
+
+#[test]
+fn strings_are_length_prefixed() {
+    // moving bytes between two adjacent strings changes the hash
+    let mut utxo = exit();
+    utxo.currency = "ETHs".to_string();
+    utxo.exitChain = "epolia".to_string();
+    assert_ne!(utxo.utxo_hash().unwrap(), EXIT_HASH);
+}
+
+#[test]
+fn rejects_invalid_fields() {
+    let mut utxo = payment();
+    utxo.public_key = "02aa".to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidPoint(
+            "public_key",
+            "02aa".to_string()
+        ))
+    );
+
+    // the identity has no compressed encoding
+    let mut utxo = payment();
+    utxo.rG = "00".to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidPoint("rG", "00".to_string()))
+    );
+
+    // scalars must be lower than the group order
+    let order = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+    let mut utxo = payment();
+    utxo.rangeProof.e = order.to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidScalar("e", order.to_string()))
+    );
+
+    let mut utxo = payment();
+    utxo.amount = "amt".to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidHex("amount", "amt".to_string()))
+    );
+
+    let mut utxo = exit();
+    utxo.transaction_hash = "txhash".to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidHex(
+            "transaction_hash",
+            "txhash".to_string()
+        ))
+    );
+
+    let mut utxo = exit();
+    utxo.amount = "-5".to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidNumber("amount", "-5".to_string()))
+    );
+
+    let mut utxo = coinbase();
+    utxo.version = "v1".to_string();
+    assert_eq!(
+        utxo.utxo_hash(),
+        Err(EncodingError::InvalidNumber("version", "v1".to_string()))
+    );
+
+    let utxo = ExitUTXO {
+        transaction_hash: "deposit".to_string(),
+        ..exit()
+    };
+    assert!(utxo.hashed().is_err());
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::{exit, point, tx_hash, utxos};
+use state_prover_core::{ExitUTXO, WireError, UTXO, UTXO_WIRE_VERSION};
+
+#[test]
+fn round_trip() {
+    for utxo in utxos() {
+        let decoded = UTXO::from_bytes(&utxo.to_bytes()).unwrap();
+        assert_eq!(decoded.to_bytes(), utxo.to_bytes());
+        assert_eq!(decoded.utxo_hash(), Ok(utxo.get_hash()));
+        assert_eq!(decoded, utxo);
+    }
+}
+
+#[test]
+fn header() {
+    for (tag, utxo) in utxos().iter().enumerate() {
+        let bytes = utxo.to_bytes();
+        assert_eq!(bytes[0], UTXO_WIRE_VERSION);
+        assert_eq!(bytes[1] as usize, tag);
+    }
+}
+
+#[test]
+fn layout() {
+    // the layout is pinned, a change here needs a new wire version
+    let utxo = UTXO::Exit(ExitUTXO {
+        transaction_hash: "t".to_string(),
+        output_index: 2,
+        public_key: "p".to_string(),
+        unlock_time: Some(5),
+        amount: "a".to_string(),
+        currency: "c".to_string(),
+        commitment: "m".to_string(),
+        exitChain: "e".to_string(),
+        hash: "h".to_string(),
+    });
+    let expected = "0101\
+        0000000174\
+        0000000000000002\
+        0000000170\
+        010000000000000005\
+        0000000161\
+        0000000163\
+        000000016d\
+        0000000165\
+        0000000168";
+    assert_eq!(hex::encode(utxo.to_bytes()), expected);
+}
+
+#[test]
+fn rejects_unknown_version() {
+    for utxo in utxos() {
+        let mut bytes = utxo.to_bytes();
+        for version in [0, UTXO_WIRE_VERSION + 1, u8::MAX] {
+            bytes[0] = version;
+            assert_eq!(
+                UTXO::from_bytes(&bytes),
+                Err(WireError::UnsupportedVersion(version))
+            );
+        }
+        bytes[0] = UTXO_WIRE_VERSION;
+        assert_eq!(UTXO::from_bytes(&bytes), Ok(utxo));
+    }
+    assert_eq!(
+        UTXO::from_bytes(&[0]),
+        Err(WireError::UnsupportedVersion(0))
+    );
+}
+
+#[test]
+fn rejects_unknown_tag() {
+    for utxo in utxos() {
+        let mut bytes = utxo.to_bytes();
+        bytes[1] = 3;
+        assert_eq!(UTXO::from_bytes(&bytes), Err(WireError::UnknownTag(3)));
+    }
+}
+
+#[test]
+fn tag_decides_the_variant() {
+    // the payload of an exit read as a coinbase does not decode
+    let mut bytes = UTXO::Exit(exit()).to_bytes();
+    bytes[1] = 2;
+    assert!(UTXO::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn rejects_truncated() {
+    assert_eq!(UTXO::from_bytes(&[]), Err(WireError::UnexpectedEnd(0)));
+    for utxo in utxos() {
+        let bytes = utxo.to_bytes();
+        for len in 0..bytes.len() {
+            // the error points at the field cut by the end of the input
+            match UTXO::from_bytes(&bytes[..len]) {
+                Err(WireError::UnexpectedEnd(offset)) => assert!(offset <= len),
+                result => panic!("{} bytes of {:?} decoded to {:?}", len, utxo, result),
+            }
+        }
+        assert_eq!(UTXO::from_bytes(&bytes), Ok(utxo));
+    }
+}
+
+#[test]
+fn rejects_trailing_bytes() {
+    for utxo in utxos() {
+        let mut bytes = utxo.to_bytes();
+        let len = bytes.len();
+        bytes.extend([0, 0]);
+        assert_eq!(UTXO::from_bytes(&bytes), Err(WireError::TrailingBytes(2)));
+        // a second UTXO after the first one is trailing bytes too
+        bytes.truncate(len);
+        bytes.extend(utxo.to_bytes());
+        assert_eq!(UTXO::from_bytes(&bytes), Err(WireError::TrailingBytes(len)));
+        assert_eq!(UTXO::from_bytes(&bytes[..len]), Ok(utxo));
+    }
+}
+
+#[test]
+fn rejects_invalid_fields() {
+    // the transaction hash starts after the header and the length prefixed "0x01"
+    let mut bytes = UTXO::Coinbase(common::coinbase()).to_bytes();
+    let string = 2 + 4 + "0x01".len();
+    bytes[string + 4] = 0xff;
+    assert_eq!(
+        UTXO::from_bytes(&bytes),
+        Err(WireError::InvalidUtf8(string))
+    );
+
+    // the unlock time flag follows the transaction hash, the output index and the public key
+    let mut bytes = UTXO::Exit(exit()).to_bytes();
+    let flag = 2 + 4 + tx_hash().len() + 8 + 4 + point("exit key").len();
+    bytes[flag] = 2;
+    assert_eq!(
+        UTXO::from_bytes(&bytes),
+        Err(WireError::InvalidOption(flag, 2))
+    );
+}
+
+#[test]
+fn rejects_oversized_lengths() {
+    let mut bytes = UTXO::Payment(Box::new(common::payment())).to_bytes();
+    bytes[2..6].copy_from_slice(&u32::MAX.to_be_bytes());
+    assert_eq!(UTXO::from_bytes(&bytes), Err(WireError::UnexpectedEnd(6)));
+}
@@ -0,0 +1,508 @@
+#!/usr/bin/env python3
+"""Golden vectors of the core tests, computed without the Rust code.
+
+Keccak-256, secp256k1 and the ABI encoder below are written from their specifications and only
+use the Python standard library, so the vectors do not depend on alloy, k256 or tiny-keccak.
+Run `python3 gen_vectors.py` and copy the values into the tests when an encoding changes.
+"""
+
+# --- Keccak-256 (the original padding, as Ethereum uses it) ---------------------------------
+
+_RC = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+_ROT = [
+    [0, 36, 3, 41, 18], [1, 44, 10, 45, 2], [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56], [27, 20, 39, 8, 14],
+]
+_MASK = (1 << 64) - 1
+
+
+def _rol(value, shift):
+    return ((value << shift) | (value >> (64 - shift))) & _MASK if shift else value
+
+
+def _keccak_f(lanes):
+    for rc in _RC:
+        c = [lanes[x][0] ^ lanes[x][1] ^ lanes[x][2] ^ lanes[x][3] ^ lanes[x][4] for x in range(5)]
+        d = [c[(x - 1) % 5] ^ _rol(c[(x + 1) % 5], 1) for x in range(5)]
+        lanes = [[lanes[x][y] ^ d[x] for y in range(5)] for x in range(5)]
+        b = [[0] * 5 for _ in range(5)]
+        for x in range(5):
+            for y in range(5):
+                b[y][(2 * x + 3 * y) % 5] = _rol(lanes[x][y], _ROT[x][y])
+        lanes = [[b[x][y] ^ (~b[(x + 1) % 5][y] & b[(x + 2) % 5][y]) for y in range(5)] for x in range(5)]
+        lanes[0][0] ^= rc
+    return lanes
+
+
+def keccak256(data):
+    rate = 136
+    padded = bytearray(data) + b"\x01" + b"\x00" * ((-len(data) - 2) % rate) + b"\x80"
+    if len(padded) % rate:
+        raise AssertionError("bad padding")
+    lanes = [[0] * 5 for _ in range(5)]
+    for offset in range(0, len(padded), rate):
+        block = padded[offset:offset + rate]
+        for i in range(rate // 8):
+            lanes[i % 5][i // 5] ^= int.from_bytes(block[8 * i:8 * i + 8], "little")
+        lanes = _keccak_f(lanes)
+    out = b"".join(lanes[i % 5][i // 5].to_bytes(8, "little") for i in range(4))
+    return out
+
+
+assert keccak256(b"").hex() == "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+assert keccak256(b"abc").hex() == "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+
+# --- secp256k1 ---------------------------------------------------------------------------
+
+P = 2**256 - 2**32 - 977
+N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+G = (
+    0x79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798,
+    0x483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8,
+)
+
+
+def point_add(a, b):
+    if a is None:
+        return b
+    if b is None:
+        return a
+    if a[0] == b[0] and (a[1] + b[1]) % P == 0:
+        return None
+    if a == b:
+        slope = 3 * a[0] * a[0] * pow(2 * a[1], -1, P) % P
+    else:
+        slope = (b[1] - a[1]) * pow(b[0] - a[0], -1, P) % P
+    x = (slope * slope - a[0] - b[0]) % P
+    return (x, (slope * (a[0] - x) - a[1]) % P)
+
+
+def point_mul(k, point):
+    result = None
+    while k:
+        if k & 1:
+            result = point_add(result, point)
+        point = point_add(point, point)
+        k >>= 1
+    return result
+
+
+def compress(point):
+    return bytes([2 + (point[1] & 1)]) + point[0].to_bytes(32, "big")
+
+
+def decompress(data):
+    x = int.from_bytes(data[1:], "big")
+    if x >= P:
+        return None
+    y_square = (pow(x, 3, P) + 7) % P
+    y = pow(y_square, (P + 1) // 4, P)
+    if y * y % P != y_square:
+        return None
+    if y & 1 != data[0] - 2:
+        y = P - y
+    return (x, y)
+
+
+def hash_to_point(data):
+    """Try-and-increment: x = keccak256(data || uint32 counter), even y."""
+    counter = 0
+    while True:
+        point = decompress(b"\x02" + keccak256(data + counter.to_bytes(4, "big")))
+        if point is not None:
+            return point
+        counter += 1
+
+
+H = hash_to_point(compress(G))
+
+
+def commit_clear(amount):
+    """Commitment of a clear amount, the blinding factor is zero."""
+    return point_mul(amount, H)
+
+
+def address(point):
+    return keccak256(point[0].to_bytes(32, "big") + point[1].to_bytes(32, "big"))[12:]
+
+# --- ABI encoding ------------------------------------------------------------------------
+# A value is (type, data) with type one of "uint", "bool", "address", "bytesN", "bytes",
+# "string", "tuple" (data: list of values) and "array" (data: list of values).
+
+
+def is_dynamic(value):
+    kind, data = value
+    if kind in ("bytes", "string", "array"):
+        return True
+    if kind == "tuple":
+        return any(is_dynamic(item) for item in data)
+    return False
+
+
+def encode_tuple(values):
+    heads, tails = [], []
+    head_size = sum(32 if is_dynamic(v) else len(encode(v)) for v in values)
+    for value in values:
+        if is_dynamic(value):
+            heads.append((head_size + sum(len(t) for t in tails)).to_bytes(32, "big"))
+            tails.append(encode(value))
+        else:
+            heads.append(encode(value))
+    return b"".join(heads) + b"".join(tails)
+
+
+def encode(value):
+    kind, data = value
+    if kind == "uint":
+        return data.to_bytes(32, "big")
+    if kind == "bool":
+        return (1 if data else 0).to_bytes(32, "big")
+    if kind == "address":
+        return bytes(12) + data
+    if kind == "bytesN":
+        return data + bytes(32 - len(data))
+    if kind in ("bytes", "string"):
+        raw = data.encode() if kind == "string" else data
+        return len(raw).to_bytes(32, "big") + raw + bytes(-len(raw) % 32)
+    if kind == "tuple":
+        return encode_tuple(data)
+    if kind == "array":
+        return len(data).to_bytes(32, "big") + encode_tuple(data)
+    raise ValueError(kind)
+
+
+def abi_encode(value):
+    """`abi.encode(value)` of a single value, a dynamic struct starts with its offset."""
+    return encode_tuple([value])
+
+
+def abi_encode_params(values):
+    """`abi.encode(a, b, ...)`, the parameters are the fields of a tuple."""
+    return encode_tuple(values)
+
+
+def b32(data):
+    return ("bytesN", data)
+
+
+def u(value):
+    return ("uint", value)
+
+
+def point_value(point):
+    """`CompressedPoint { bytes1 prefix; bytes32 x; }`."""
+    encoded = compress(point)
+    return ("tuple", [("bytesN", encoded[:1]), ("bytesN", encoded[1:])])
+
+
+def domain(tag):
+    return b32(keccak256(tag.encode()))
+
+# --- UTXO fixtures, the same as core/tests/common/mod.rs ----------------------------------
+
+
+def scalar(label):
+    """Deterministic scalar of a fixture, `hash_to_scalar(label)`."""
+    return int.from_bytes(keccak256(label.encode()), "big") % N
+
+
+def fixture_point(label):
+    return point_mul(scalar(label), G)
+
+
+TX_HASH = keccak256(b"transaction")
+ENCRYPTED_AMOUNT = keccak256(b"amount") + keccak256(b"blinding factor")
+ROUNDS = 6
+
+
+def range_proof_value():
+    return ("tuple", [
+        point_value(fixture_point("V")),
+        point_value(fixture_point("A")),
+        point_value(fixture_point("S")),
+        point_value(fixture_point("T1")),
+        point_value(fixture_point("T2")),
+        b32(scalar("tx").to_bytes(32, "big")),
+        b32(scalar("txbf").to_bytes(32, "big")),
+        b32(scalar("e").to_bytes(32, "big")),
+        b32(scalar("a0").to_bytes(32, "big")),
+        b32(scalar("b0").to_bytes(32, "big")),
+        ("array", [point_value(fixture_point("L%d" % i)) for i in range(ROUNDS)]),
+        ("array", [point_value(fixture_point("R%d" % i)) for i in range(ROUNDS)]),
+    ])
+
+
+def payment_value():
+    return ("tuple", [
+        domain("CypherZer0/PaymentUTXO/v1"),
+        u(1),
+        b32(TX_HASH),
+        u(3),
+        point_value(fixture_point("payment key")),
+        ("bool", True),
+        u(7),
+        ("bytes", ENCRYPTED_AMOUNT),
+        ("string", "ETH"),
+        point_value(fixture_point("V")),
+        b32(keccak256(abi_encode(range_proof_value()))),
+        point_value(fixture_point("payment rG")),
+    ])
+
+
+def exit_value():
+    return ("tuple", [
+        domain("CypherZer0/ExitUTXO/v1"),
+        b32(TX_HASH),
+        u(1),
+        point_value(fixture_point("exit key")),
+        ("bool", False),
+        u(0),
+        u(5),
+        ("string", "ETH"),
+        point_value(commit_clear(5)),
+        ("string", "sepolia"),
+    ])
+
+
+def coinbase_value():
+    return ("tuple", [
+        domain("CypherZer0/CoinbaseUTXO/v1"),
+        u(1),
+        b32(bytes(32)),
+        u(0),
+        point_value(fixture_point("coinbase key")),
+        ("bool", False),
+        u(0),
+        u(10),
+        ("string", "ETH"),
+        point_value(commit_clear(10)),
+        point_value(fixture_point("coinbase rG")),
+    ])
+
+
+def utxo_vectors():
+    print("H:", compress(H).hex())
+    for name, value in [("payment", payment_value()), ("exit", exit_value()),
+                        ("coinbase", coinbase_value())]:
+        print("%s hash:" % name, keccak256(abi_encode(value)).hex())
+
+
+# --- Sparse Merkle tree -------------------------------------------------------------------
+
+
+def sparse_root(entries, height=256):
+    """Root of the depth 256 tree of `entries` (key -> value), an empty subtree hashes to zero.
+
+    The child of a node at `height` is picked by the bit `height - 1` of the key, counted from the
+    least significant bit, so the root splits on the most significant one."""
+    if not entries:
+        return bytes(32)
+    if height == 0:
+        ((key, value),) = entries.items()
+        return keccak256(key + value)
+    bit = height - 1
+    left = {k: v for k, v in entries.items() if not int.from_bytes(k, "big") >> bit & 1}
+    right = {k: v for k, v in entries.items() if int.from_bytes(k, "big") >> bit & 1}
+    left_root, right_root = sparse_root(left, bit), sparse_root(right, bit)
+    if left_root == bytes(32) and right_root == bytes(32):
+        return bytes(32)
+    return keccak256(left_root + right_root)
+
+
+def sparse_entries():
+    """Two random keys, and a neighbour of the first one that only differs in the last bit."""
+    a, b = keccak256(b"a"), keccak256(b"b")
+    neighbour = a[:31] + bytes([a[31] ^ 1])
+    return {a: bytes(32), b: bytes(32), neighbour: keccak256(b"value")}
+
+
+def sparse_vectors():
+    entries = sparse_entries()
+    print("sparse root:", sparse_root(entries).hex())
+    a = keccak256(b"a")
+    print("sparse root without a:", sparse_root({k: v for k, v in entries.items() if k != a}).hex())
+
+
+# --- Block accumulator and headers -------------------------------------------------------
+
+
+def accumulator_root(leaves, depth=32):
+    """Root of the depth 32 tree of `leaves`, padded with empty subtrees, mixed with the count.
+
+    Computed level by level over the whole tree, not from a frontier."""
+    level, zero = list(leaves), bytes(32)
+    for _ in range(depth):
+        if len(level) % 2:
+            level.append(zero)
+        level = [keccak256(level[i] + level[i + 1]) for i in range(0, len(level), 2)]
+        zero = keccak256(zero + zero)
+    node = level[0] if level else zero
+    return keccak256(node + len(leaves).to_bytes(32, "big"))
+
+
+def accumulator_leaves(count):
+    """Leaves of core/tests/accumulator.rs, `keccak256(i.to_be_bytes())` for a u64 `i`."""
+    return [keccak256(i.to_bytes(8, "big")) for i in range(count)]
+
+
+def header_value():
+    """Header of core/tests/block.rs."""
+    return ("tuple", [
+        domain("CypherZer0/BlockHeader/v1"),
+        u(2),
+        b32(keccak256(b"parent")),
+        u(1700000001),
+        b32(keccak256(b"tx root")),
+        b32(keccak256(b"pre utxo root")),
+        b32(keccak256(b"post utxo root")),
+        ("address", bytes.fromhex("00000000000000000000000000000000000000a1")),
+    ])
+
+
+def accumulator_vectors():
+    for count in (0, 1, 2, 3, 5, 8, 13):
+        root = accumulator_root(accumulator_leaves(count))
+        print("accumulator root of %d leaves:" % count, root.hex())
+
+
+def transactions_value():
+    """Transactions of core/tests/block.rs, whose hashes are `keccak256("tx <i>")`."""
+    hashes = [b32(keccak256(b"tx %d" % i)) for i in range(3)]
+    return ("tuple", [domain("CypherZer0/BlockTransactions/v1"), ("array", hashes)])
+
+
+def block_vectors():
+    print("header hash:", keccak256(abi_encode(header_value())).hex())
+    print("transactions root:", keccak256(abi_encode(transactions_value())).hex())
+
+
+# --- OpenZeppelin multiproofs -------------------------------------------------------------
+# Port of `makeMerkleTree` and `getMultiProof` of @openzeppelin/merkle-tree (src/core.ts): the
+# tree is an array, node `i` has children `2i + 1` and `2i + 2`, and the leaves are stored at the
+# end in reverse order.
+
+
+def hash_pair(a, b):
+    return keccak256(min(a, b) + max(a, b))
+
+
+def oz_tree(leaves):
+    tree = [None] * (2 * len(leaves) - 1)
+    for i, leaf in enumerate(leaves):
+        tree[len(tree) - 1 - i] = leaf
+    for i in range(len(tree) - 1 - len(leaves), -1, -1):
+        tree[i] = hash_pair(tree[2 * i + 1], tree[2 * i + 2])
+    return tree
+
+
+def oz_multi_proof(tree, indices):
+    """`getMultiProof` of the tree indices, leaves first."""
+    indices = sorted(indices, reverse=True)
+    stack, proof, flags = list(indices), [], []
+    while stack and stack[0] > 0:
+        j = stack.pop(0)
+        s = j + 1 if j % 2 else j - 1
+        if stack and s == stack[0]:
+            flags.append(True)
+            stack.pop(0)
+        else:
+            flags.append(False)
+            proof.append(tree[s])
+        stack.append((j - 1) // 2)
+    if not indices:
+        proof.append(tree[0])
+    return [tree[i] for i in indices], proof, flags
+
+
+def multiproof_vectors():
+    for count, leaf_indices in ((8, [1, 2, 5]), (5, [0, 3, 4])):
+        tree = oz_tree(accumulator_leaves(count))
+        leaves, proof, flags = oz_multi_proof(tree, [len(tree) - 1 - i for i in leaf_indices])
+        print("multiproof of leaves %s of %d:" % (leaf_indices, count))
+        print("  root:", tree[0].hex())
+        print("  leaves:", [leaf.hex() for leaf in leaves])
+        print("  proof:", [node.hex() for node in proof])
+        print("  flags:", flags)
+
+
+# --- Withdrawal tree ----------------------------------------------------------------------
+
+
+def standard_root(leaves):
+    """Root of `StandardMerkleTree.of`, which sorts the leaves before building the array tree."""
+    return oz_tree(sorted(leaves))[0]
+
+
+def withdrawal_leaf(utxo_hash, recipient, amount, currency, exit_chain):
+    values = [b32(utxo_hash), ("address", recipient), u(amount), ("string", currency),
+              ("string", exit_chain)]
+    return keccak256(keccak256(abi_encode_params(values)))
+
+
+def withdrawal_leaves():
+    """Withdrawals of core/tests/withdrawal.rs: the exit fixture, then four plain ones, the ones of
+    host/tests/withdrawal.rs."""
+    exit_hash = keccak256(abi_encode(exit_value()))
+    leaves = [withdrawal_leaf(exit_hash, address(fixture_point("exit key")), 5 * 10**9, "ETH",
+                              "sepolia")]
+    for i in range(1, 5):
+        leaves.append(withdrawal_leaf(keccak256(b"withdrawal %d" % i), bytes([i]) * 20,
+                                      i * 10**9, "ETH", "sepolia"))
+    return leaves
+
+
+def withdrawal_vectors():
+    leaves = withdrawal_leaves()
+    print("exit withdrawal leaf:", leaves[0].hex())
+    print("withdrawal root:", standard_root(leaves).hex())
+    print("withdrawal root of 2:", standard_root(leaves[:2]).hex())
+    print("withdrawal root of the plain ones:", standard_root(leaves[1:]).hex())
+
+
+# --- Deposits -----------------------------------------------------------------------------
+
+
+def deposit_value(label, amount):
+    """`Deposit` of `deposit_tx(label, amount)` of core/tests/common/mod.rs."""
+    return ("tuple", [b32(keccak256(label.encode())), point_value(fixture_point(label)),
+                      u(amount * 10**9), ("string", "ETH")])
+
+
+def deposit_vectors():
+    deposits = [deposit_value("alice", 10), deposit_value("bob", 20)]
+    print("deposit hash:", keccak256(abi_encode(("array", deposits))).hex())
+
+
+# --- Journal ------------------------------------------------------------------------------
+
+
+def journal_value():
+    """`StateTransitionJournal` of core/tests/journal.rs."""
+    return ("tuple", [b32(keccak256(b"prev root")), b32(keccak256(b"new root")), u(3), u(5),
+                      b32(keccak256(b"prev batch")), b32(keccak256(b"batch")),
+                      b32(keccak256(b"deposits")), b32(keccak256(b"withdrawals"))])
+
+
+def journal_vectors():
+    encoded = abi_encode(journal_value())
+    print("journal:")
+    for offset in range(0, len(encoded), 32):
+        print("  ", encoded[offset:offset + 32].hex())
+
+
+if __name__ == "__main__":
+    utxo_vectors()
+    sparse_vectors()
+    accumulator_vectors()
+    block_vectors()
+    multiproof_vectors()
+    withdrawal_vectors()
+    deposit_vectors()
+    journal_vectors()
@@ -0,0 +1,125 @@
+mod common;
+
+use alloy_primitives::{Address, B256, U256};
+use common::exit;
+use state_prover_core::{
+    keccak256, verify_proof, withdrawal_root, withdrawal_tree, Hash, MerkleTree,
+    StandardMerkleTree, Withdrawal, WithdrawalError, AMOUNT_UNIT_WEI,
+};
+
+// Leaf of the withdrawal of `exit()` and roots of `StandardMerkleTree.of` over the leaves of
+// `withdrawals()`, computed by the port of tests/vectors/gen_vectors.py.
+const EXIT_LEAF: &str = "2017c2450b98e37663b1148023cf2b53330f7723a274061ea1fc846edeed9e09";
+const ROOT: &str = "10effc0c152edf6067105972bd312ad9709824e43b83144466d7eb0aaf75d7cf";
+const ROOT_OF_2: &str = "4ed25935f0e7a8efdbd1bdff020438f4ad9d8aed43db6e01b65a9cc6c3da964e";
+
+/// The withdrawal of the exit fixture, then four plain ones.
+fn withdrawals() -> Vec<Withdrawal> {
+    let mut withdrawals = vec![Withdrawal::from_exit(&exit()).unwrap()];
+    for i in 1..5u8 {
+        withdrawals.push(Withdrawal {
+            utxoHash: B256::from(keccak256(format!("withdrawal {}", i).as_bytes())),
+            recipient: Address::from([i; 20]),
+            amount: U256::from(i as u64 * AMOUNT_UNIT_WEI),
+            currency: "ETH".to_string(),
+            exitChain: "sepolia".to_string(),
+        });
+    }
+    withdrawals
+}
+
+#[test]
+fn golden_withdrawal_roots() {
+    let withdrawals = withdrawals();
+    assert_eq!(hex::encode(withdrawals[0].leaf_hash()), EXIT_LEAF);
+    assert_eq!(hex::encode(withdrawal_root(&withdrawals)), ROOT);
+    assert_eq!(hex::encode(withdrawal_root(&withdrawals[..2])), ROOT_OF_2);
+    assert_eq!(withdrawal_root(&[]), [0u8; 32]);
+
+    // the leaves are sorted, the order of the exits does not matter
+    let mut reversed = withdrawals.clone();
+    reversed.reverse();
+    assert_eq!(hex::encode(withdrawal_root(&reversed)), ROOT);
+
+    // the promoted tree of five leaves has another shape
+    let leaves: Vec<Hash> = withdrawals.iter().map(Withdrawal::leaf_hash).collect();
+    assert_ne!(
+        MerkleTree::new(leaves).root(),
+        withdrawal_tree(&withdrawals).root()
+    );
+}
+
+#[test]
+fn exit_withdrawal() {
+    let withdrawal = Withdrawal::from_exit(&exit()).unwrap();
+    assert_eq!(
+        withdrawal.utxoHash.to_string(),
+        format!("0x{}", exit().hash)
+    );
+    assert_eq!(withdrawal.amount, U256::from(5 * AMOUNT_UNIT_WEI));
+    assert_eq!(withdrawal.exitChain, "sepolia");
+
+    let mut invalid = exit();
+    invalid.public_key = "02aa".to_string();
+    assert_eq!(
+        Withdrawal::from_exit(&invalid),
+        Err(WithdrawalError::InvalidRecipient("02aa".to_string()))
+    );
+}
+
+#[test]
+fn claims_verify_against_the_root() {
+    let withdrawals = withdrawals();
+    let tree = withdrawal_tree(&withdrawals);
+    let root = tree.root().unwrap();
+    for withdrawal in &withdrawals {
+        // the claims of `host::withdrawal_claims`, the leaf is found in the sorted leaves
+        let leaf = withdrawal.leaf_hash();
+        let index = tree.locate_leaf(&leaf).unwrap();
+        let proof = tree.generate_proof(index).unwrap();
+        assert!(verify_proof(&root, &leaf, &proof));
+
+        // a claim of another amount or for another recipient is rejected
+        let mut other = withdrawal.clone();
+        other.amount += U256::from(1);
+        assert!(!verify_proof(&root, &other.leaf_hash(), &proof));
+        let mut other = withdrawal.clone();
+        other.recipient = Address::from([0xff; 20]);
+        assert!(!verify_proof(&root, &other.leaf_hash(), &proof));
+    }
+    assert_eq!(tree.generate_proof(withdrawals.len()), None);
+    assert_eq!(tree.locate_leaf(&keccak256(b"not a leaf")), None);
+}
+
+#[test]
+fn standard_tree_layout() {
+    let leaves: Vec<Hash> = (0..5u64).map(|i| keccak256(&i.to_be_bytes())).collect();
+    let tree = StandardMerkleTree::new(leaves.clone());
+    let mut sorted = leaves.clone();
+    sorted.sort();
+    assert_eq!(tree.leaves(), sorted);
+
+    // a complete tree of five leaves, stored in reverse: the two first leaves sit one level
+    // below the others
+    let depths: Vec<usize> = (0..5)
+        .map(|index| tree.generate_proof(index).unwrap().len())
+        .collect();
+    assert_eq!(depths, vec![3, 3, 2, 2, 2]);
+
+    // equal leaves are all kept, the first one is located
+    let tree = StandardMerkleTree::new(vec![leaves[1], leaves[0], leaves[1]]);
+    let index = tree.locate_leaf(&leaves[1]).unwrap();
+    assert_eq!(tree.leaves().len(), 3);
+    assert_eq!(tree.leaves()[index], leaves[1]);
+    assert!(!tree.leaves()[..index].contains(&leaves[1]));
+    assert!(verify_proof(
+        &tree.root().unwrap(),
+        &leaves[1],
+        &tree.generate_proof(index).unwrap()
+    ));
+
+    let single = StandardMerkleTree::new(vec![leaves[0]]);
+    assert_eq!(single.root(), Some(leaves[0]));
+    assert_eq!(single.generate_proof(0), Some(vec![]));
+    assert_eq!(StandardMerkleTree::new(vec![]).root(), None);
+}
@@ -0,0 +1,37 @@
+// Runs the merkle benchmark guest for a few tree sizes and prints the cycles spent building each
+// tree, legacy hex string nodes against 32 bytes nodes.
+use methods::MERKLE_BENCH_ELF;
+use risc0_zkvm::{serde::from_slice, ExecutorEnv, ExecutorImpl};
+use state_prover_core::keccak256;
+
+const SIZES: [u64; 5] = [16, 64, 256, 1024, 4096];
+
+fn main() {
+    println!(
+        "{:>8} {:>14} {:>14} {:>8}",
+        "leaves", "legacy cycles", "bytes cycles", "speedup"
+    );
+    for size in SIZES {
+        let leaves: Vec<String> = (0..size)
+            .map(|i| hex::encode(keccak256(&i.to_be_bytes())))
+            .collect();
+        let env = ExecutorEnv::builder()
+            .write(&leaves)
+            .unwrap()
+            .build()
+            .unwrap();
+        let session = ExecutorImpl::from_elf(env, MERKLE_BENCH_ELF)
+            .unwrap()
+            .run()
+            .unwrap();
+        let journal = session.journal.expect("Benchmark has no journal");
+        let (count, legacy, bytes): (u64, u64, u64) = from_slice(&journal.bytes).unwrap();
+        println!(
+            "{:>8} {:>14} {:>14} {:>7.1}x",
+            count,
+            legacy,
+            bytes,
+            legacy as f64 / bytes as f64
+        );
+    }
+}
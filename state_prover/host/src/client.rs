@@ -0,0 +1,236 @@
+use bonsai_sdk::alpha::responses::SnarkReceipt;
+use bonsai_sdk::alpha::{Client, SessionId, SnarkId};
+use risc0_zkvm::{compute_image_id, Receipt};
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum ProverError {
+    Backend(String), // the proving service could not be reached or rejected a request
+    Failed(String, String), // final status, error message
+    Aborted(String), // error message
+    TimedOut(Duration), // time spent polling
+    Cancelled,
+    InvalidResponse(String),
+}
+
+impl Display for ProverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ProverError::Backend(e) => write!(f, "Proving backend error: {}", e),
+            ProverError::Failed(status, e) => {
+                write!(f, "Workflow exited: {} - | err: {}", status, e)
+            }
+            ProverError::Aborted(e) => write!(f, "Workflow aborted: {}", e),
+            ProverError::TimedOut(elapsed) => {
+                write!(f, "Workflow still running after {:?}", elapsed)
+            }
+            ProverError::Cancelled => write!(f, "Polling cancelled"),
+            ProverError::InvalidResponse(e) => write!(f, "Invalid response: {}", e),
+        }
+    }
+}
+
+impl Error for ProverError {}
+
+/// Status of a remote job.
+#[derive(Debug, Clone)]
+pub enum JobStatus<T> {
+    Running(String), // state reported by the backend
+    Succeeded(T),
+    Failed(String, String), // status, error message
+    Aborted(String),        // error message
+}
+
+/// How long and how often a job is polled.
+#[derive(Debug, Clone)]
+pub struct PollConfig {
+    pub interval: Duration,
+    pub timeout: Duration,
+    pub cancel: Arc<AtomicBool>, // set to true to stop polling
+}
+
+impl Default for PollConfig {
+    fn default() -> Self {
+        PollConfig {
+            interval: Duration::from_secs(15),
+            timeout: Duration::from_secs(60 * 60),
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl PollConfig {
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+}
+
+/// Longest sleep between two checks of the cancel flag.
+pub const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Polls `status` until the job leaves the running state, the timeout elapses or polling is
+/// cancelled.
+///
+/// Between two polls, the cancel flag is checked at least every `CANCEL_CHECK_INTERVAL` and the
+/// wait never goes past the timeout, so both stop polling without waiting for a full interval.
+pub fn poll<T>(
+    config: &PollConfig,
+    mut status: impl FnMut() -> Result<JobStatus<T>, ProverError>,
+) -> Result<T, ProverError> {
+    let start = Instant::now();
+    let deadline = start + config.timeout;
+    loop {
+        if config.is_cancelled() {
+            return Err(ProverError::Cancelled);
+        }
+        match status()? {
+            JobStatus::Succeeded(output) => return Ok(output),
+            JobStatus::Failed(status, e) => return Err(ProverError::Failed(status, e)),
+            JobStatus::Aborted(e) => return Err(ProverError::Aborted(e)),
+            JobStatus::Running(state) => {
+                eprintln!("Current state: {} - continue polling...", state)
+            }
+        }
+        let next = (Instant::now() + config.interval).min(deadline);
+        loop {
+            let now = Instant::now();
+            if now >= next {
+                break;
+            }
+            if config.is_cancelled() {
+                return Err(ProverError::Cancelled);
+            }
+            std::thread::sleep((next - now).min(CANCEL_CHECK_INTERVAL));
+        }
+        if Instant::now() >= deadline {
+            return Err(ProverError::TimedOut(start.elapsed()));
+        }
+    }
+}
+
+/// Remote proving service: a STARK session for a guest run, then its SNARK wrapping.
+pub trait ProverClient {
+    /// Starts proving `elf` on `input` (already encoded for the guest), returns the session id.
+    fn create_session(&self, elf: &[u8], input: &[u8]) -> Result<String, ProverError>;
+    fn session_status(&self, session: &str) -> Result<JobStatus<Receipt>, ProverError>;
+    /// Starts the SNARK wrapping of a succeeded session, returns the snark id.
+    fn create_snark(&self, session: &str) -> Result<String, ProverError>;
+    fn snark_status(&self, snark: &str) -> Result<JobStatus<SnarkReceipt>, ProverError>;
+}
+
+/// Proves `elf` on `input`, returns the session id and its receipt.
+pub fn prove_session(
+    client: &dyn ProverClient,
+    elf: &[u8],
+    input: &[u8],
+    config: &PollConfig,
+) -> Result<(String, Receipt), ProverError> {
+    let session = client.create_session(elf, input)?;
+    eprintln!("Created session: {}", session);
+    let receipt = poll(config, || client.session_status(&session))?;
+    Ok((session, receipt))
+}
+
+/// Wraps the STARK of a succeeded session into a SNARK, returns the snark id and its receipt.
+pub fn prove_snark(
+    client: &dyn ProverClient,
+    session: &str,
+    config: &PollConfig,
+) -> Result<(String, SnarkReceipt), ProverError> {
+    let snark = client.create_snark(session)?;
+    eprintln!("Created snark session: {}", snark);
+    let receipt = poll(config, || client.snark_status(&snark))?;
+    Ok((snark, receipt))
+}
+
+pub struct BonsaiClient {
+    client: Client,
+}
+
+impl BonsaiClient {
+    /// Reads `BONSAI_API_URL` and `BONSAI_API_KEY`.
+    pub fn from_env() -> Result<Self, ProverError> {
+        let client = Client::from_env(risc0_zkvm::VERSION).map_err(backend)?;
+        Ok(BonsaiClient { client })
+    }
+}
+
+impl ProverClient for BonsaiClient {
+    fn create_session(&self, elf: &[u8], input: &[u8]) -> Result<String, ProverError> {
+        // Compute the image_id, then upload the ELF with the image_id as its key.
+        let image_id = hex::encode(compute_image_id(elf).map_err(backend)?);
+        self.client
+            .upload_img(&image_id, elf.to_vec())
+            .map_err(backend)?;
+        let input_id = self.client.upload_input(input.to_vec()).map_err(backend)?;
+        let session = self
+            .client
+            .create_session(image_id, input_id, vec![])
+            .map_err(backend)?;
+        Ok(session.uuid)
+    }
+
+    fn session_status(&self, session: &str) -> Result<JobStatus<Receipt>, ProverError> {
+        let res = SessionId::new(session.to_string())
+            .status(&self.client)
+            .map_err(backend)?;
+        job_status(&res.status, res.error_msg, res.state, || {
+            // Download the receipt, containing the output
+            let receipt_url = res.receipt_url.clone().ok_or_else(|| {
+                ProverError::InvalidResponse("missing receipt on completed session".to_string())
+            })?;
+            let receipt_buf = self.client.download(&receipt_url).map_err(backend)?;
+            bincode::deserialize(&receipt_buf)
+                .map_err(|e| ProverError::InvalidResponse(e.to_string()))
+        })
+    }
+
+    fn create_snark(&self, session: &str) -> Result<String, ProverError> {
+        let snark = self
+            .client
+            .create_snark(session.to_string())
+            .map_err(backend)?;
+        Ok(snark.uuid)
+    }
+
+    fn snark_status(&self, snark: &str) -> Result<JobStatus<SnarkReceipt>, ProverError> {
+        let res = SnarkId::new(snark.to_string())
+            .status(&self.client)
+            .map_err(backend)?;
+        job_status(&res.status, res.error_msg, None, || {
+            res.output.clone().ok_or_else(|| {
+                ProverError::InvalidResponse("missing SnarkReceipt on completed snark".to_string())
+            })
+        })
+    }
+}
+
+fn backend(e: impl Display) -> ProverError {
+    ProverError::Backend(e.to_string())
+}
+
+/// Maps a Bonsai status, `output` is only called once the job succeeded.
+fn job_status<T>(
+    status: &str,
+    error_msg: Option<String>,
+    state: Option<String>,
+    output: impl FnOnce() -> Result<T, ProverError>,
+) -> Result<JobStatus<T>, ProverError> {
+    match status {
+        "RUNNING" => Ok(JobStatus::Running(state.unwrap_or_default())),
+        "SUCCEEDED" => Ok(JobStatus::Succeeded(output()?)),
+        "ABORTED" => Ok(JobStatus::Aborted(error_msg.unwrap_or_default())),
+        _ => Ok(JobStatus::Failed(
+            status.to_string(),
+            error_msg.unwrap_or_default(),
+        )),
+    }
+}
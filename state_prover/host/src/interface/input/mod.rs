@@ -1,2 +0,0 @@
-pub mod input; 
-pub use input::*;
\ No newline at end of file
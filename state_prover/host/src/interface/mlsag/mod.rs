@@ -1,4 +0,0 @@
-pub mod mlsag;
-pub mod point;
-pub use mlsag::MLSAG;
-pub use point::Point;
@@ -1,2 +0,0 @@
-mod utxo;
-pub use utxo::*;
@@ -0,0 +1,10 @@
+pub mod client;
+pub use client::*;
+pub mod prover;
+pub use prover::*;
+pub mod seal;
+pub use seal::*;
+pub mod verify;
+pub use verify::*;
+pub mod withdrawal;
+pub use withdrawal::*;
@@ -0,0 +1,220 @@
+// These constants represent the RISC-V ELF and the image ID generated by risc0-build.
+// The ELF is used for proving and the ID is used for verification.
+use methods::{STATE_PROVER_ELF, STATE_PROVER_ID};
+
+use crate::client::{prove_session, BonsaiClient, PollConfig};
+use risc0_zkvm::{
+    serde::to_vec, ExecutorEnv, ExecutorImpl, InnerReceipt, LocalProver, Prover as _, Receipt,
+};
+use state_prover_core::transition::{execute, TransitionError};
+use state_prover_core::{Inputs, StateTransitionJournal};
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Environment variable selecting the proving mode.
+pub const PROVER_MODE_ENV: &str = "PROVER_MODE";
+
+/// How the guest is run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProverMode {
+    Execute, // run the guest without proving, only the journal is produced
+    DevMode, // fake receipt of the executed session, no proof
+    Local,   // full STARK proof on this machine
+    Bonsai,  // STARK proof on Bonsai, requires BONSAI_API_URL and BONSAI_API_KEY
+}
+
+#[derive(Debug)]
+pub struct UnknownProverMode(pub String);
+
+impl Display for UnknownProverMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Unknown prover mode {}, expected one of execute, dev, local, bonsai",
+            self.0
+        )
+    }
+}
+
+impl Error for UnknownProverMode {}
+
+impl FromStr for ProverMode {
+    type Err = UnknownProverMode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "execute" => Ok(ProverMode::Execute),
+            "dev" | "devmode" => Ok(ProverMode::DevMode),
+            "local" => Ok(ProverMode::Local),
+            "bonsai" => Ok(ProverMode::Bonsai),
+            _ => Err(UnknownProverMode(s.to_string())),
+        }
+    }
+}
+
+impl ProverMode {
+    /// Reads the mode from `PROVER_MODE`, Bonsai when unset.
+    pub fn from_env() -> Result<Self, UnknownProverMode> {
+        match std::env::var(PROVER_MODE_ENV) {
+            Ok(mode) => mode.parse(),
+            Err(_) => Ok(ProverMode::Bonsai),
+        }
+    }
+
+    pub fn prover(&self) -> Box<dyn Prover> {
+        match self {
+            ProverMode::Execute => Box::new(ExecuteProver),
+            ProverMode::DevMode => Box::new(DevModeProver),
+            ProverMode::Local => Box::new(LocalStarkProver),
+            ProverMode::Bonsai => Box::new(BonsaiProver {
+                poll: PollConfig::default(),
+            }),
+        }
+    }
+}
+
+/// Result of a guest run.
+pub struct ProverOutput {
+    pub journal: Vec<u8>,
+    pub receipt: Option<Receipt>, // verified receipt, fake in dev mode, none when only executed
+    pub bonsai_session: Option<String>, // Bonsai session, required to request a SNARK
+}
+
+pub trait Prover {
+    /// Runs the guest on `input`.
+    fn run(&self, input: &Inputs) -> Result<ProverOutput, Box<dyn Error>>;
+
+    /// Checks the transition on the host first, an invalid transition never reaches the prover.
+    fn prove(&self, input: &Inputs) -> Result<ProverOutput, Box<dyn Error>> {
+        preflight(input)?;
+        self.run(input)
+    }
+}
+
+#[derive(Debug)]
+pub enum PreflightError {
+    InvalidStateTransition { expected: String, computed: String }, // block roots
+    Rejected(TransitionError),
+}
+
+impl Display for PreflightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PreflightError::InvalidStateTransition { expected, computed } => write!(
+                f,
+                "Invalid state transition: expected root {} but computed {}",
+                expected, computed
+            ),
+            PreflightError::Rejected(e) => write!(f, "Invalid state transition: {}", e),
+        }
+    }
+}
+
+impl Error for PreflightError {}
+
+/// Runs the checks of the guest on the host and returns the journal the guest would commit.
+pub fn preflight(input: &Inputs) -> Result<StateTransitionJournal, PreflightError> {
+    execute(input).map_err(|e| match e {
+        TransitionError::RootMismatch(expected, computed) => {
+            PreflightError::InvalidStateTransition { expected, computed }
+        }
+        e => PreflightError::Rejected(e),
+    })
+}
+
+/// Runs the guest in the executor, no proof.
+pub struct ExecuteProver;
+
+impl Prover for ExecuteProver {
+    fn run(&self, input: &Inputs) -> Result<ProverOutput, Box<dyn Error>> {
+        let env = executor_env(input)?;
+        let session = ExecutorImpl::from_elf(env, STATE_PROVER_ELF)?.run()?;
+        Ok(ProverOutput {
+            journal: session
+                .journal
+                .map(|journal| journal.bytes)
+                .unwrap_or_default(),
+            receipt: None,
+            bonsai_session: None,
+        })
+    }
+}
+
+/// Runs the guest in the executor and wraps the claim of the session in a fake receipt, as the
+/// risc0 dev mode prover does, without setting `RISC0_DEV_MODE` for the whole process.
+///
+/// The exit code and the journal are the ones of the execution, but the receipt proves nothing:
+/// it only verifies where `RISC0_DEV_MODE` is set, and is never sent to a verifier contract.
+pub struct DevModeProver;
+
+impl Prover for DevModeProver {
+    fn run(&self, input: &Inputs) -> Result<ProverOutput, Box<dyn Error>> {
+        let env = executor_env(input)?;
+        let session = ExecutorImpl::from_elf(env, STATE_PROVER_ELF)?.run()?;
+        let claim = session.get_claim()?;
+        let journal = session
+            .journal
+            .map(|journal| journal.bytes)
+            .unwrap_or_default();
+        eprintln!("WARNING: dev mode, the receipt is not a proof");
+        Ok(ProverOutput {
+            receipt: Some(Receipt::new(InnerReceipt::Fake { claim }, journal.clone())),
+            journal,
+            bonsai_session: None,
+        })
+    }
+}
+
+/// Proves the guest execution on this machine.
+pub struct LocalStarkProver;
+
+impl Prover for LocalStarkProver {
+    fn run(&self, input: &Inputs) -> Result<ProverOutput, Box<dyn Error>> {
+        if std::env::var("RISC0_DEV_MODE").is_ok() {
+            return Err("RISC0_DEV_MODE is set, unset it for a real local proof".into());
+        }
+        prove_locally(input)
+    }
+}
+
+/// Proves the guest execution on Bonsai.
+pub struct BonsaiProver {
+    pub poll: PollConfig,
+}
+
+impl Prover for BonsaiProver {
+    fn run(&self, input: &Inputs) -> Result<ProverOutput, Box<dyn Error>> {
+        let client = BonsaiClient::from_env()?;
+
+        // Prepare input data for `env::read`.
+        let input_data = to_vec(&input.to_bytes())?;
+        let input_data: &[u8] = bytemuck::cast_slice(&input_data);
+
+        let (session, receipt) = prove_session(&client, STATE_PROVER_ELF, input_data, &self.poll)?;
+        receipt.verify(STATE_PROVER_ID)?;
+        println!("Proof verified!");
+        Ok(ProverOutput {
+            journal: receipt.journal.bytes.clone(),
+            receipt: Some(receipt),
+            bonsai_session: Some(session),
+        })
+    }
+}
+
+fn executor_env(input: &Inputs) -> Result<ExecutorEnv<'static>, Box<dyn Error>> {
+    let env = ExecutorEnv::builder().write(&input.to_bytes())?.build()?;
+    Ok(env)
+}
+
+fn prove_locally(input: &Inputs) -> Result<ProverOutput, Box<dyn Error>> {
+    let env = executor_env(input)?;
+    let receipt = LocalProver::new("local").prove(env, STATE_PROVER_ELF)?;
+    receipt.verify(STATE_PROVER_ID)?;
+    println!("Proof verified!");
+    Ok(ProverOutput {
+        journal: receipt.journal.bytes.clone(),
+        receipt: Some(receipt),
+        bonsai_session: None,
+    })
+}
@@ -0,0 +1,127 @@
+use crate::client::{prove_snark, PollConfig, ProverClient};
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_sol_types::{sol, SolValue};
+use bonsai_sdk::alpha::responses::SnarkReceipt;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::Display;
+
+sol! {
+    /// Groth16 proof points, in the layout expected by the RISC Zero Solidity verifier.
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+    struct Groth16Seal {
+        uint256[2] a;
+        uint256[2][2] b; // G2 point, each coordinate (imaginary, real) as Bonsai returns it
+        uint256[2] c;
+    }
+}
+
+#[derive(Debug)]
+pub enum SealError {
+    InvalidShape(&'static str),         // field of the snark
+    InvalidLength(&'static str, usize), // field of the snark, length in bytes
+}
+
+impl Display for SealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SealError::InvalidShape(field) => write!(f, "Unexpected shape for {}", field),
+            SealError::InvalidLength(field, length) => {
+                write!(f, "Incorrect length {} for {}", length, field)
+            }
+        }
+    }
+}
+
+impl Error for SealError {}
+
+/// Everything needed to verify a state transition on chain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProofBundle {
+    pub snark_id: String,
+    pub seal: Groth16Seal,
+    pub public_signals: Vec<U256>,
+    pub post_state_digest: B256,
+    pub journal: Bytes,
+}
+
+impl ProofBundle {
+    pub fn from_snark_receipt(snark_id: String, receipt: &SnarkReceipt) -> Result<Self, SealError> {
+        let snark = &receipt.snark;
+        if snark.a.len() != 2 || snark.c.len() != 2 {
+            return Err(SealError::InvalidShape("a or c"));
+        }
+        if snark.b.len() != 2 || snark.b.iter().any(|row| row.len() != 2) {
+            return Err(SealError::InvalidShape("b"));
+        }
+        let seal = Groth16Seal {
+            a: [word(&snark.a[0], "a")?, word(&snark.a[1], "a")?],
+            b: [
+                [word(&snark.b[0][0], "b")?, word(&snark.b[0][1], "b")?],
+                [word(&snark.b[1][0], "b")?, word(&snark.b[1][1], "b")?],
+            ],
+            c: [word(&snark.c[0], "c")?, word(&snark.c[1], "c")?],
+        };
+        let public_signals = snark
+            .public
+            .iter()
+            .map(|signal| word(signal, "public"))
+            .collect::<Result<_, _>>()?;
+        let post_state_digest: [u8; 32] =
+            receipt
+                .post_state_digest
+                .as_slice()
+                .try_into()
+                .map_err(|_| {
+                    SealError::InvalidLength("post_state_digest", receipt.post_state_digest.len())
+                })?;
+        Ok(ProofBundle {
+            snark_id,
+            seal,
+            public_signals,
+            post_state_digest: B256::from(post_state_digest),
+            journal: Bytes::from(receipt.journal.clone()),
+        })
+    }
+
+    /// ABI encoded seal, the `bytes seal` argument of the verifier.
+    pub fn seal_calldata(&self) -> Vec<u8> {
+        self.seal.abi_encode()
+    }
+
+    /// ABI encoding of `(bytes journal, bytes32 postStateDigest, bytes seal)`.
+    pub fn abi_encode(&self) -> Vec<u8> {
+        (
+            self.journal.clone(),
+            self.post_state_digest,
+            Bytes::from(self.seal_calldata()),
+        )
+            .abi_encode_params()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Wraps the STARK of a succeeded session into a Groth16 proof.
+pub fn prove_bundle(
+    client: &dyn ProverClient,
+    session: &str,
+    config: &PollConfig,
+) -> Result<ProofBundle, Box<dyn Error>> {
+    let (snark_id, receipt) = prove_snark(client, session, config)?;
+    Ok(ProofBundle::from_snark_receipt(snark_id, &receipt)?)
+}
+
+/// Big-endian 32 bytes word.
+fn word(bytes: &[u8], field: &'static str) -> Result<U256, SealError> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| SealError::InvalidLength(field, bytes.len()))?;
+    Ok(U256::from_be_bytes(bytes))
+}
@@ -0,0 +1,109 @@
+use crate::seal::ProofBundle;
+use alloy_primitives::U256;
+use risc0_groth16::Seal;
+use risc0_zkvm::sha::{Digest, Digestible};
+use risc0_zkvm::{
+    CompactReceipt, ExitCode, InnerReceipt, MaybePruned, Output, Receipt, ReceiptClaim,
+};
+use std::error::Error;
+use std::fmt::Display;
+
+#[derive(Debug)]
+pub enum VerificationError {
+    PublicSignals(usize),  // number of public signals
+    ClaimMismatch(Digest), // claim digest expected from the image ID, post state and journal
+    InvalidProof(String),
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VerificationError::PublicSignals(count) => {
+                write!(f, "Expected 4 public signals, got {}", count)
+            }
+            VerificationError::ClaimMismatch(claim) => write!(
+                f,
+                "Public signals do not commit to claim {}, wrong image ID, post state digest or journal",
+                claim
+            ),
+            VerificationError::InvalidProof(e) => write!(f, "Invalid Groth16 proof: {}", e),
+        }
+    }
+}
+
+impl Error for VerificationError {}
+
+/// Claim proven by a successful run of `image_id` ending in `post_state_digest` and writing
+/// `journal`, with no assumption.
+pub fn receipt_claim(image_id: Digest, post_state_digest: Digest, journal: &[u8]) -> ReceiptClaim {
+    ReceiptClaim {
+        pre: MaybePruned::Pruned(image_id),
+        post: MaybePruned::Pruned(post_state_digest),
+        exit_code: ExitCode::Halted(0),
+        input: Digest::ZERO,
+        output: Some(Output {
+            journal: MaybePruned::Pruned(journal.digest()),
+            assumptions: MaybePruned::Pruned(Digest::ZERO),
+        })
+        .into(),
+    }
+}
+
+/// Splits a digest into the two 128 bits public signals of the Groth16 circuit, as the RISC Zero
+/// verifier contract does: the byte reversed digest, low half then high half.
+pub fn split_digest(digest: &Digest) -> (U256, U256) {
+    let mut reversed = [0u8; 32];
+    reversed.copy_from_slice(digest.as_bytes());
+    reversed.reverse();
+    let mut low = [0u8; 32];
+    let mut high = [0u8; 32];
+    low[16..].copy_from_slice(&reversed[16..]);
+    high[16..].copy_from_slice(&reversed[..16]);
+    (U256::from_be_bytes(low), U256::from_be_bytes(high))
+}
+
+/// Verifies the Groth16 proof of a bundle for `image_id` against the RISC Zero verifying key.
+///
+/// The claim is rebuilt from the image ID, the post state digest and the journal of the bundle,
+/// so a proof of another guest or for another journal is rejected without any pairing check.
+pub fn verify_bundle(bundle: &ProofBundle, image_id: Digest) -> Result<(), VerificationError> {
+    if bundle.public_signals.len() != 4 {
+        return Err(VerificationError::PublicSignals(
+            bundle.public_signals.len(),
+        ));
+    }
+    let claim = receipt_claim(
+        image_id,
+        Digest::from(bundle.post_state_digest.0),
+        &bundle.journal,
+    );
+    let (c0, c1) = split_digest(&claim.digest());
+    if bundle.public_signals[2] != c0 || bundle.public_signals[3] != c1 {
+        return Err(VerificationError::ClaimMismatch(claim.digest()));
+    }
+
+    let seal = Seal {
+        a: bundle.seal.a.iter().map(word).collect(),
+        b: bundle
+            .seal
+            .b
+            .iter()
+            .map(|row| row.iter().map(word).collect())
+            .collect(),
+        c: bundle.seal.c.iter().map(word).collect(),
+    };
+    let receipt = Receipt::new(
+        InnerReceipt::Compact(CompactReceipt {
+            seal: seal.to_vec(),
+            claim,
+        }),
+        bundle.journal.to_vec(),
+    );
+    receipt
+        .verify(image_id)
+        .map_err(|e| VerificationError::InvalidProof(e.to_string()))
+}
+
+fn word(value: &U256) -> Vec<u8> {
+    value.to_be_bytes::<32>().to_vec()
+}
@@ -0,0 +1,66 @@
+use crate::prover::{preflight, PreflightError};
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+use state_prover_core::transition::batch_withdrawals;
+use state_prover_core::{verify_proof, withdrawal_tree, Inputs, Withdrawal};
+use std::collections::BTreeMap;
+
+/// What the bridge needs to release a withdrawal: the data of its leaf and its proof against the
+/// `withdrawalRoot` of the journal.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawalClaim {
+    pub utxo_hash: B256,
+    pub recipient: Address,
+    pub amount: U256,
+    pub currency: String,
+    pub exit_chain: String,
+    pub proof: Vec<B256>,
+}
+
+impl WithdrawalClaim {
+    pub fn withdrawal(&self) -> Withdrawal {
+        Withdrawal {
+            utxoHash: self.utxo_hash,
+            recipient: self.recipient,
+            amount: self.amount,
+            currency: self.currency.clone(),
+            exitChain: self.exit_chain.clone(),
+        }
+    }
+
+    /// Checks the claim against a withdrawal root, as the bridge does.
+    pub fn verify(&self, withdrawal_root: &B256) -> bool {
+        let leaf = self.withdrawal().leaf_hash();
+        let proof: Vec<_> = self.proof.iter().map(|sibling| sibling.0).collect();
+        verify_proof(&withdrawal_root.0, &leaf, &proof)
+    }
+}
+
+/// Claims of every exit output of the batch by recipient, once the transition is checked.
+pub fn withdrawal_claims(
+    input: &Inputs,
+) -> Result<BTreeMap<Address, Vec<WithdrawalClaim>>, PreflightError> {
+    preflight(input)?;
+    let withdrawals = batch_withdrawals(&input.blocks).map_err(PreflightError::Rejected)?;
+    let tree = withdrawal_tree(&withdrawals);
+    let mut claims: BTreeMap<Address, Vec<WithdrawalClaim>> = BTreeMap::new();
+    for withdrawal in withdrawals {
+        // the leaves of the tree are sorted, a withdrawal is found by its leaf
+        let proof = tree
+            .locate_leaf(&withdrawal.leaf_hash())
+            .and_then(|index| tree.generate_proof(index))
+            .expect("One leaf per withdrawal");
+        claims
+            .entry(withdrawal.recipient)
+            .or_default()
+            .push(WithdrawalClaim {
+                utxo_hash: withdrawal.utxoHash,
+                recipient: withdrawal.recipient,
+                amount: withdrawal.amount,
+                currency: withdrawal.currency,
+                exit_chain: withdrawal.exitChain,
+                proof: proof.into_iter().map(B256::from).collect(),
+            });
+    }
+    Ok(claims)
+}
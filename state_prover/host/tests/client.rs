@@ -0,0 +1,128 @@
+mod common;
+
+use common::{snark_receipt, MockProverClient};
+use host::{poll, prove_snark, JobStatus, PollConfig, ProverError};
+use std::time::{Duration, Instant};
+
+fn config() -> PollConfig {
+    PollConfig {
+        interval: Duration::from_millis(5),
+        timeout: Duration::from_millis(200),
+        ..PollConfig::default()
+    }
+}
+
+#[test]
+fn polls_until_success() {
+    let client = MockProverClient::new().with_snark_statuses(vec![
+        Ok(JobStatus::Running("queued".to_string())),
+        Ok(JobStatus::Running("proving".to_string())),
+        Ok(JobStatus::Succeeded(snark_receipt())),
+    ]);
+    let (snark, receipt) = prove_snark(&client, "session", &config()).unwrap();
+    assert_eq!(snark, "mock-snark");
+    assert_eq!(receipt.journal, vec![42]);
+    assert_eq!(client.status_calls(), 3);
+}
+
+#[test]
+fn reports_failures() {
+    let client = MockProverClient::new().with_snark_statuses(vec![Ok(JobStatus::Failed(
+        "FAILED".to_string(),
+        "guest panicked".to_string(),
+    ))]);
+    match prove_snark(&client, "session", &config()) {
+        Err(ProverError::Failed(status, e)) => {
+            assert_eq!(status, "FAILED");
+            assert_eq!(e, "guest panicked");
+        }
+        other => panic!("unexpected result {:?}", other.map(|(snark, _)| snark)),
+    }
+}
+
+#[test]
+fn reports_aborts() {
+    let client = MockProverClient::new().with_snark_statuses(vec![Ok(JobStatus::Aborted(
+        "cancelled by user".to_string(),
+    ))]);
+    assert!(matches!(
+        prove_snark(&client, "session", &config()),
+        Err(ProverError::Aborted(_))
+    ));
+}
+
+#[test]
+fn propagates_backend_errors() {
+    let client = MockProverClient::new().with_snark_statuses(vec![
+        Ok(JobStatus::Running("proving".to_string())),
+        Err(ProverError::Backend("connection reset".to_string())),
+    ]);
+    assert!(matches!(
+        prove_snark(&client, "session", &config()),
+        Err(ProverError::Backend(_))
+    ));
+    assert_eq!(client.status_calls(), 2);
+}
+
+#[test]
+fn times_out() {
+    let client = MockProverClient::new();
+    assert!(matches!(
+        prove_snark(&client, "session", &config()),
+        Err(ProverError::TimedOut(_))
+    ));
+    assert!(client.status_calls() > 1);
+}
+
+#[test]
+fn stops_when_cancelled() {
+    let client = MockProverClient::new();
+    let config = config();
+    config.cancel();
+    assert!(matches!(
+        prove_snark(&client, "session", &config),
+        Err(ProverError::Cancelled)
+    ));
+    assert_eq!(client.status_calls(), 0);
+}
+
+#[test]
+fn stops_when_cancelled_while_waiting() {
+    let config = PollConfig {
+        interval: Duration::from_secs(60),
+        timeout: Duration::from_secs(600),
+        ..PollConfig::default()
+    };
+    let cancel = config.clone();
+    let start = Instant::now();
+    let canceller = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(50));
+        cancel.cancel();
+    });
+    let result: Result<(), _> = poll(&config, || Ok(JobStatus::Running("proving".to_string())));
+    canceller.join().unwrap();
+    assert!(matches!(result, Err(ProverError::Cancelled)));
+    // well before the next poll
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn times_out_without_waiting_for_the_next_poll() {
+    let config = PollConfig {
+        interval: Duration::from_secs(60),
+        timeout: Duration::from_millis(100),
+        ..PollConfig::default()
+    };
+    let start = Instant::now();
+    let mut calls = 0;
+    let result: Result<(), _> = poll(&config, || {
+        calls += 1;
+        Ok(JobStatus::Running("proving".to_string()))
+    });
+    assert!(matches!(
+        result,
+        Err(ProverError::TimedOut(elapsed)) if elapsed >= config.timeout
+    ));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(calls, 1);
+}
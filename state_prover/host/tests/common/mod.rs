@@ -0,0 +1,89 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use bonsai_sdk::alpha::responses::{Groth16Seal, SnarkReceipt};
+use host::{JobStatus, ProverClient, ProverError};
+use risc0_zkvm::Receipt;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Scripted client: every status call pops the next status, `Running` once the script is over.
+#[derive(Default)]
+pub struct MockProverClient {
+    pub session_statuses: Mutex<VecDeque<Result<JobStatus<Receipt>, ProverError>>>,
+    pub snark_statuses: Mutex<VecDeque<Result<JobStatus<SnarkReceipt>, ProverError>>>,
+    pub status_calls: Mutex<usize>,
+}
+
+impl MockProverClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_session_statuses(
+        self,
+        statuses: Vec<Result<JobStatus<Receipt>, ProverError>>,
+    ) -> Self {
+        *self.session_statuses.lock().unwrap() = statuses.into();
+        self
+    }
+
+    pub fn with_snark_statuses(
+        self,
+        statuses: Vec<Result<JobStatus<SnarkReceipt>, ProverError>>,
+    ) -> Self {
+        *self.snark_statuses.lock().unwrap() = statuses.into();
+        self
+    }
+
+    /// Number of status calls made so far.
+    pub fn status_calls(&self) -> usize {
+        *self.status_calls.lock().unwrap()
+    }
+}
+
+impl ProverClient for MockProverClient {
+    fn create_session(&self, _elf: &[u8], _input: &[u8]) -> Result<String, ProverError> {
+        Ok("mock-session".to_string())
+    }
+
+    fn session_status(&self, _session: &str) -> Result<JobStatus<Receipt>, ProverError> {
+        *self.status_calls.lock().unwrap() += 1;
+        self.session_statuses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Ok(JobStatus::Running("mock".to_string())))
+    }
+
+    fn create_snark(&self, _session: &str) -> Result<String, ProverError> {
+        Ok("mock-snark".to_string())
+    }
+
+    fn snark_status(&self, _snark: &str) -> Result<JobStatus<SnarkReceipt>, ProverError> {
+        *self.status_calls.lock().unwrap() += 1;
+        self.snark_statuses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Ok(JobStatus::Running("mock".to_string())))
+    }
+}
+
+/// Snark whose 32 bytes words are filled with their rank in the seal, `a`, `b` row by row then
+/// `c`, and with 9 for the public signals.
+pub fn snark_receipt() -> SnarkReceipt {
+    SnarkReceipt {
+        snark: Groth16Seal {
+            a: vec![vec![1; 32], vec![2; 32]],
+            b: vec![
+                vec![vec![3; 32], vec![4; 32]],
+                vec![vec![5; 32], vec![6; 32]],
+            ],
+            c: vec![vec![7; 32], vec![8; 32]],
+            public: vec![vec![9; 32]; 4],
+        },
+        post_state_digest: vec![0; 32],
+        journal: vec![42],
+    }
+}
@@ -0,0 +1,38 @@
+use host::{preflight, PreflightError};
+use state_prover_core::transition::EMPTY_ROOT;
+use state_prover_core::{
+    keccak256, Block, BlockHeader, BlockWitness, Inputs, MerkleAccumulator, StateCommitment,
+};
+
+#[test]
+fn preflight_reports_both_roots() {
+    // the input claims a block root at t the frontier does not have
+    let frontier = MerkleAccumulator::new();
+    let computed = hex::encode(frontier.root().unwrap());
+    let claimed = hex::encode(keccak256(b"other chain"));
+    let input = Inputs {
+        state_t_1: EMPTY_ROOT.to_string(),
+        state_t: claimed.clone(),
+        block_frontier: frontier,
+        parent_proof: vec![],
+        blocks: vec![Block {
+            header: BlockHeader {
+                height: 1,
+                parent_hash: EMPTY_ROOT.to_string(),
+                timestamp: 0,
+                tx_root: EMPTY_ROOT.to_string(),
+                pre_utxo_root: EMPTY_ROOT.to_string(),
+                post_utxo_root: EMPTY_ROOT.to_string(),
+                sequencer: "0x0000000000000000000000000000000000000000".to_string(),
+            },
+            transactions: vec![],
+        }],
+        state: StateCommitment::default(),
+        witnesses: vec![BlockWitness::default()],
+    };
+    assert!(matches!(
+        preflight(&input),
+        Err(PreflightError::InvalidStateTransition { expected, computed: root })
+            if expected == claimed && root == computed
+    ));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_sol_types::{sol_data, SolType};
+use common::snark_receipt;
+use host::{ProofBundle, SealError};
+
+fn word(rank: u8) -> U256 {
+    U256::from_be_bytes([rank; 32])
+}
+
+fn bundle() -> ProofBundle {
+    ProofBundle::from_snark_receipt("snark".to_string(), &snark_receipt()).unwrap()
+}
+
+#[test]
+fn keeps_the_order_of_the_points() {
+    let bundle = bundle();
+    assert_eq!(bundle.seal.a, [word(1), word(2)]);
+    // the coordinates of b are already swapped for the verifier, they are not swapped again
+    assert_eq!(bundle.seal.b, [[word(3), word(4)], [word(5), word(6)]]);
+    assert_eq!(bundle.seal.c, [word(7), word(8)]);
+    assert_eq!(bundle.public_signals, vec![word(9); 4]);
+    assert_eq!(bundle.post_state_digest, B256::ZERO);
+    assert_eq!(bundle.journal, Bytes::from(vec![42]));
+}
+
+#[test]
+fn seal_calldata() {
+    // a, b row by row then c, one word each
+    let expected: Vec<u8> = (1..=8).flat_map(|rank| [rank; 32]).collect();
+    let bundle = bundle();
+    assert_eq!(bundle.seal_calldata(), expected);
+
+    // the arguments of the verifier, as `abi_encode` lays them out
+    type Arguments = (sol_data::Bytes, sol_data::FixedBytes<32>, sol_data::Bytes);
+    let (journal, post_state_digest, seal) =
+        Arguments::abi_decode_params(&bundle.abi_encode(), true).unwrap();
+    assert_eq!(journal, bundle.journal.to_vec());
+    assert_eq!(post_state_digest, bundle.post_state_digest);
+    assert_eq!(seal.to_vec(), expected);
+}
+
+#[test]
+fn json_round_trip() {
+    let bundle = bundle();
+    let json = bundle.to_json().unwrap();
+    assert_eq!(ProofBundle::from_json(&json).unwrap(), bundle);
+    assert!(ProofBundle::from_json("{}").is_err());
+}
+
+#[test]
+fn rejects_malformed_snarks() {
+    let mut receipt = snark_receipt();
+    receipt.snark.b[1].pop();
+    assert!(matches!(
+        ProofBundle::from_snark_receipt("snark".to_string(), &receipt),
+        Err(SealError::InvalidShape("b"))
+    ));
+
+    let mut receipt = snark_receipt();
+    receipt.snark.c[0].push(0);
+    assert!(matches!(
+        ProofBundle::from_snark_receipt("snark".to_string(), &receipt),
+        Err(SealError::InvalidLength("c", 33))
+    ));
+
+    let mut receipt = snark_receipt();
+    receipt.post_state_digest.truncate(31);
+    assert!(matches!(
+        ProofBundle::from_snark_receipt("snark".to_string(), &receipt),
+        Err(SealError::InvalidLength("post_state_digest", 31))
+    ));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use alloy_primitives::{Bytes, B256, U256};
+use common::snark_receipt;
+use host::{receipt_claim, split_digest, verify_bundle, ProofBundle, VerificationError};
+use risc0_zkvm::sha::{Digest, Digestible};
+
+// Control root of the RISC Zero verifier and the two public signals it is split into, the
+// `CONTROL_ROOT_0` and `CONTROL_ROOT_1` of the verifier contract.
+const CONTROL_ROOT: &str = "a516a057c9fbf5629106300934d48e0e775d4230e41e503347cad96fcbde7e2e";
+const CONTROL_ROOT_0: &str = "0e8ed4340930069162f5fbc957a016a5";
+const CONTROL_ROOT_1: &str = "2e7edecb6fd9ca4733501ee430425d77";
+
+fn digest(hex: &str) -> Digest {
+    let bytes: [u8; 32] = hex::decode(hex).unwrap().try_into().unwrap();
+    Digest::from(bytes)
+}
+
+fn signal(hex: &str) -> U256 {
+    U256::from_str_radix(hex, 16).unwrap()
+}
+
+fn image_id() -> Digest {
+    Digest::from([1u8; 32])
+}
+
+/// Bundle of the snark fixture whose public signals commit to the claim of `image_id()`, its
+/// seal is not a valid proof.
+fn bundle() -> ProofBundle {
+    let mut bundle =
+        ProofBundle::from_snark_receipt("snark".to_string(), &snark_receipt()).unwrap();
+    bundle.post_state_digest = B256::from([2u8; 32]);
+    let claim = receipt_claim(
+        image_id(),
+        Digest::from(bundle.post_state_digest.0),
+        &bundle.journal,
+    );
+    let (c0, c1) = split_digest(&claim.digest());
+    bundle.public_signals[2] = c0;
+    bundle.public_signals[3] = c1;
+    bundle
+}
+
+#[test]
+fn splits_the_control_root() {
+    assert_eq!(
+        split_digest(&digest(CONTROL_ROOT)),
+        (signal(CONTROL_ROOT_0), signal(CONTROL_ROOT_1))
+    );
+    assert_eq!(split_digest(&Digest::ZERO), (U256::ZERO, U256::ZERO));
+}
+
+#[test]
+fn rejects_another_claim() {
+    // the claim matches, only the pairing check fails
+    assert!(matches!(
+        verify_bundle(&bundle(), image_id()),
+        Err(VerificationError::InvalidProof(_))
+    ));
+
+    let mut tampered = bundle();
+    tampered.journal = Bytes::from(vec![43]);
+    assert!(matches!(
+        verify_bundle(&tampered, image_id()),
+        Err(VerificationError::ClaimMismatch(_))
+    ));
+
+    assert!(matches!(
+        verify_bundle(&bundle(), Digest::from([3u8; 32])),
+        Err(VerificationError::ClaimMismatch(_))
+    ));
+
+    let mut tampered = bundle();
+    tampered.post_state_digest = B256::from([3u8; 32]);
+    assert!(matches!(
+        verify_bundle(&tampered, image_id()),
+        Err(VerificationError::ClaimMismatch(_))
+    ));
+}
+
+#[test]
+fn rejects_missing_public_signals() {
+    let mut bundle = bundle();
+    bundle.public_signals.pop();
+    assert!(matches!(
+        verify_bundle(&bundle, image_id()),
+        Err(VerificationError::PublicSignals(3))
+    ));
+}
@@ -0,0 +1,71 @@
+use alloy_primitives::{Address, B256, U256};
+use host::WithdrawalClaim;
+use state_prover_core::{keccak256, withdrawal_tree, Withdrawal, AMOUNT_UNIT_WEI};
+
+// Root of `StandardMerkleTree.of` over the leaves of `withdrawals()`, computed by the port of
+// core/tests/vectors/gen_vectors.py.
+const ROOT: &str = "b2d68ec33f0c5cdabe7baf0313c4fb6f26fa2f4dbdde7fe842398ace230b9d6b";
+
+fn withdrawals() -> Vec<Withdrawal> {
+    (1..5u8)
+        .map(|i| Withdrawal {
+            utxoHash: B256::from(keccak256(format!("withdrawal {}", i).as_bytes())),
+            recipient: Address::from([i; 20]),
+            amount: U256::from(i as u64 * AMOUNT_UNIT_WEI),
+            currency: "ETH".to_string(),
+            exitChain: "sepolia".to_string(),
+        })
+        .collect()
+}
+
+/// Claim of a withdrawal, with its proof in the tree of the batch as `withdrawal_claims` builds it.
+fn claim(withdrawals: &[Withdrawal], index: usize) -> WithdrawalClaim {
+    let tree = withdrawal_tree(withdrawals);
+    let withdrawal = &withdrawals[index];
+    let proof = tree
+        .locate_leaf(&withdrawal.leaf_hash())
+        .and_then(|leaf| tree.generate_proof(leaf))
+        .unwrap();
+    WithdrawalClaim {
+        utxo_hash: withdrawal.utxoHash,
+        recipient: withdrawal.recipient,
+        amount: withdrawal.amount,
+        currency: withdrawal.currency.clone(),
+        exit_chain: withdrawal.exitChain.clone(),
+        proof: proof.into_iter().map(B256::from).collect(),
+    }
+}
+
+#[test]
+fn claims_verify_against_the_standard_merkle_tree_root() {
+    let withdrawals = withdrawals();
+    let root: B256 = format!("0x{}", ROOT).parse().unwrap();
+    assert_eq!(withdrawal_tree(&withdrawals).root(), Some(root.0));
+    for index in 0..withdrawals.len() {
+        let claim = claim(&withdrawals, index);
+        assert_eq!(claim.withdrawal(), withdrawals[index]);
+        assert!(claim.verify(&root));
+
+        // the bridge pays the amount and the recipient of the leaf, nothing else
+        let mut other = claim.clone();
+        other.amount += U256::from(1);
+        assert!(!other.verify(&root));
+        let mut other = claim.clone();
+        other.recipient = Address::from([0xff; 20]);
+        assert!(!other.verify(&root));
+        let mut other = claim.clone();
+        other.proof.pop();
+        assert!(!other.verify(&root));
+    }
+    assert!(!claim(&withdrawals, 0).verify(&B256::ZERO));
+}
+
+#[test]
+fn claims_serialize_to_json() {
+    let claim = claim(&withdrawals(), 2);
+    let json = serde_json::to_string(&claim).unwrap();
+    assert_eq!(
+        serde_json::from_str::<WithdrawalClaim>(&json).unwrap(),
+        claim
+    );
+}
@@ -0,0 +1,27 @@
+#![no_main]
+
+// Cycle count of the legacy hex string merkle tree against the 32 bytes one.
+use risc0_zkvm::guest::env;
+risc0_zkvm::guest::entry!(main);
+use state_prover_core::merkleTree;
+use state_prover_core::{Leaf, MerkleTree};
+
+fn main() {
+    // hex encoded leaves
+    let leaves: Vec<String> = env::read();
+    let leaves: Vec<Leaf> = leaves.into_iter().map(Leaf::Hex).collect();
+
+    let start = env::cycle_count();
+    let legacy = merkleTree::MerkleTree::new(&leaves).expect("Failed to create Merkle Tree");
+    let legacy_cycles = env::cycle_count() - start;
+
+    let start = env::cycle_count();
+    let tree = MerkleTree::from_leaves(&leaves).expect("Failed to create Merkle Tree");
+    let cycles = env::cycle_count() - start;
+
+    let legacy_root = legacy.root.expect("Failed to get root").data;
+    let root = hex::encode(tree.root().expect("Failed to get root"));
+    assert_eq!(legacy_root, root, "Roots do not match");
+
+    env::commit(&(leaves.len() as u64, legacy_cycles as u64, cycles as u64));
+}
@@ -1,2 +0,0 @@
-pub mod merkleTree; 
-pub use merkleTree::*;
\ No newline at end of file
@@ -16,24 +16,19 @@
 // to the Bonsai proving service and publish the received proofs directly
 // to your deployed app contract.
 
-use alloy_primitives::U256;
-use alloy_sol_types::{sol, SolInterface, SolValue};
+use alloy_sol_types::{sol, SolCall, SolType};
 use anyhow::{Context, Result};
 use apps::{BonsaiProver, TxSender};
 use clap::Parser;
+use host::preflight;
 use methods::STATE_PROVER_ELF;
-use serde::{Deserialize, Serialize};
+use state_prover_core::{Inputs, StateTransitionJournal};
+use std::path::PathBuf;
 
-#[derive(Serialize, Deserialize, Debug,Clone)]
-pub struct Inputs {
-    pub state_t_1: String, // merkle root of the state at t+1 (all the blocks)
-    pub state_t: String, // merkle root of the state at t (all the blocks)
-    pub blocks_hash: Vec<String>, // hash of each block
-}
-// `IEvenNumber` interface automatically generated via the alloy `sol!` macro.
+// `IStateRoot` interface automatically generated via the alloy `sol!` macro.
 sol! {
-    interface IEvenNumber {
-        function set(uint256 x, bytes32 post_state_digest, bytes calldata seal);
+    interface IStateRoot {
+        function submitStateTransition(bytes calldata journal, bytes32 postStateDigest, bytes calldata seal);
     }
 }
 
@@ -57,9 +52,9 @@ struct Args {
     #[clap(long)]
     contract: String,
 
-    /// The input to provide to the guest binary
+    /// JSON file holding the `Inputs` to provide to the guest binary
     #[clap(short, long)]
-    input: Inputs,
+    input: PathBuf,
 }
 
 fn main() -> Result<()> {
@@ -74,27 +69,47 @@ fn main() -> Result<()> {
         &args.contract,
     )?;
 
-    // ABI encode the input for the guest binary, to match what the `is_even` guest
-    // code expects.
-    let input = Inputs ({
-        state_t_1: "71d8979cbfae9b197a4fbcc7d387b1fae9560e2f284d30b4e90c80f6bc074f57".to_string(),
-        state_t: "1".to_string(),
-        blocks_hash: vec!["2".to_string()],
-    }).abi_encode();
+    // Read the input of the transition.
+    let input: Inputs = serde_json::from_str(
+        &std::fs::read_to_string(&args.input).context("reading input file")?,
+    )
+    .context("decoding input file")?;
+
+    // Refuse invalid transitions before spending anything on a proof.
+    let expected = preflight(&input)?;
+
+    // Encode the input for the guest binary, to match what the `state_prover` guest
+    // code expects: `env::read::<Vec<u8>>()` of the bincode encoded `Inputs`.
+    let input = risc0_zkvm::serde::to_vec(&input.to_bytes())?;
+    let input: &[u8] = bytemuck::cast_slice(&input);
 
     // Send an off-chain proof request to the Bonsai proving service.
-    let (journal, post_state_digest, seal) = BonsaiProver::prove(STATE_PROVER_ELF, &input)?;
+    let bundle = BonsaiProver::prove(STATE_PROVER_ELF, input)?;
 
     // Decode the journal. Must match what was written in the guest with
-    // `env::commit_slice`.
-    let x = U256::abi_decode(11, true).context("decoding journal data")?;
-
-    // Encode the function call for `IEvenNumber.set(x)`.
-    let calldata = IEvenNumber::IEvenNumberCalls::set(IEvenNumber::setCall {
-        x,
-        post_state_digest,
-        seal,
-    })
+    // `env::commit_slice`, and the preflight, down to the `prevBatchHash` linking the batch to
+    // the blocks the contract holds.
+    let journal = StateTransitionJournal::abi_decode(&bundle.journal, true)
+        .context("decoding journal data")?;
+    anyhow::ensure!(journal == expected, "journal does not match the preflight");
+    log::info!(
+        "Blocks {} to {}, batch hash {} -> {}, state root {} -> {}",
+        journal.fromBlock,
+        journal.toBlock,
+        journal.prevBatchHash,
+        journal.batchHash,
+        journal.prevRoot,
+        journal.newRoot
+    );
+
+    // Encode the function call for `IStateRoot.submitStateTransition`. The journal is passed as
+    // is: the contract needs its exact bytes to rebuild the claim digest from sha256(journal),
+    // and reads the roots and the block range with `abi.decode`.
+    let calldata = IStateRoot::submitStateTransitionCall {
+        journal: bundle.journal.clone(),
+        postStateDigest: bundle.post_state_digest,
+        seal: bundle.seal_calldata(),
+    }
     .abi_encode();
 
     // Send the calldata to Ethereum.
@@ -102,4 +117,4 @@ fn main() -> Result<()> {
     runtime.block_on(tx_sender.send(calldata))?;
 
     Ok(())
-}
\ No newline at end of file
+}
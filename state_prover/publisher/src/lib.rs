@@ -20,14 +20,12 @@
 // under active development. As such, this library might change to adapt to
 // the upstream changes.
 
-use std::time::Duration;
-
-use alloy_primitives::FixedBytes;
-use anyhow::{Context, Result};
-use bonsai_sdk::alpha as bonsai_sdk;
+use anyhow::{anyhow, Result};
 use ethers::prelude::*;
-use risc0_ethereum_contracts::groth16::Seal;
-use risc0_zkvm::{compute_image_id, Receipt};
+use host::{
+    prove_bundle, prove_session, verify_bundle, BonsaiClient, PollConfig, ProofBundle, ProverClient,
+};
+use risc0_zkvm::compute_image_id;
 
 /// Wrapper of a `SignerMiddleware` client to send transactions to the given
 /// contract's `Address`.
@@ -73,88 +71,37 @@ impl TxSender {
 /// An implementation of a Prover that runs on Bonsai.
 pub struct BonsaiProver {}
 impl BonsaiProver {
-    /// Generates a snark proof as a triplet (`Vec<u8>`, `FixedBytes<32>`,
-    /// `Vec<u8>) for the given elf and input.
-    pub fn prove(elf: &[u8], input: &[u8]) -> Result<(Vec<u8>, FixedBytes<32>, Vec<u8>)> {
-        let client = bonsai_sdk::Client::from_env(risc0_zkvm::VERSION)?;
+    /// Generates a snark proof bundle for the given elf and input, verified locally.
+    pub fn prove(elf: &[u8], input: &[u8]) -> Result<ProofBundle> {
+        Self::prove_with(
+            &BonsaiClient::from_env()?,
+            elf,
+            input,
+            &PollConfig::default(),
+        )
+    }
 
-        // Compute the image_id, then upload the ELF with the image_id as its key.
+    /// Same as `prove` with the given client and polling configuration.
+    pub fn prove_with(
+        client: &dyn ProverClient,
+        elf: &[u8],
+        input: &[u8],
+        config: &PollConfig,
+    ) -> Result<ProofBundle> {
         let image_id = compute_image_id(elf)?;
-        let image_id_hex = image_id.to_string();
-        client.upload_img(&image_id_hex, elf.to_vec())?;
-        log::info!("Image ID: 0x{}", image_id_hex);
-
-        // Prepare input data and upload it.
-        let input_id = client.upload_input(input.to_vec())?;
+        log::info!("Image ID: 0x{}", image_id);
 
         // Start a session running the prover.
-        let session = client.create_session(image_id_hex, input_id, vec![])?;
-        log::info!("Created session: {}", session.uuid);
-        let _receipt = loop {
-            let res = session.status(&client)?;
-            if res.status == "RUNNING" {
-                log::info!(
-                    "Current status: {} - state: {} - continue polling...",
-                    res.status,
-                    res.state.unwrap_or_default()
-                );
-                std::thread::sleep(Duration::from_secs(15));
-                continue;
-            }
-            if res.status == "SUCCEEDED" {
-                // Download the receipt, containing the output.
-                let receipt_url = res
-                    .receipt_url
-                    .context("API error, missing receipt on completed session")?;
-
-                let receipt_buf = client.download(&receipt_url)?;
-                let receipt: Receipt = bincode::deserialize(&receipt_buf)?;
-
-                break receipt;
-            }
-
-            panic!(
-                "Workflow exited: {} - | err: {}",
-                res.status,
-                res.error_msg.unwrap_or_default()
-            );
-        };
+        let (session, _receipt) = prove_session(client, elf, input, config)?;
 
         // Fetch the snark.
-        let snark_session = client.create_snark(session.uuid)?;
-        log::info!("Created snark session: {}", snark_session.uuid);
-        let snark_receipt = loop {
-            let res = snark_session.status(&client)?;
-            match res.status.as_str() {
-                "RUNNING" => {
-                    log::info!("Current status: {} - continue polling...", res.status,);
-                    std::thread::sleep(Duration::from_secs(15));
-                    continue;
-                }
-                "SUCCEEDED" => {
-                    break res.output.context("No snark generated :(")?;
-                }
-                _ => {
-                    panic!(
-                        "Workflow exited: {} err: {}",
-                        res.status,
-                        res.error_msg.unwrap_or_default()
-                    );
-                }
-            }
-        };
-
-        let snark = snark_receipt.snark;
-        log::debug!("Snark proof!: {snark:?}");
-
-        let seal = Seal::abi_encode(snark).context("Read seal")?;
-        let post_state_digest: FixedBytes<32> = snark_receipt
-            .post_state_digest
-            .as_slice()
-            .try_into()
-            .context("Read post_state_digest")?;
-        let journal = snark_receipt.journal;
-
-        Ok((journal, post_state_digest, seal))
+        let bundle = prove_bundle(client, &session, config).map_err(|e| anyhow!("{}", e))?;
+        log::debug!("Snark proof!: {:?}", bundle.seal);
+
+        // Check the proof off chain, a bad proof must not cost a reverted transaction.
+        verify_bundle(&bundle, image_id)?;
+        log::info!("Groth16 proof verified");
+
+        Ok(bundle)
     }
-}
\ No newline at end of file
+}